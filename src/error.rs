@@ -1,3 +1,5 @@
+use crate::poker::card::Card;
+
 /// This is the core error type for the
 /// Poker_RS library. It uses `thiserror` to provide
 /// readable error messages
@@ -7,16 +9,36 @@ pub enum Error {
     UnexpectedRankChar,
     #[error("Unable to parse suit")]
     UnexpectedSuitChar,
+    #[error("{0} is not a card rank value, expected 2 to 14")]
+    InvalidRankInt(i8),
+    #[error("Bitset {0:#x} has bits set above the 52 cards")]
+    InvalidCardBitset(u64),
+    #[error("Unable to read input: {0}")]
+    ReadFailed(String),
+    #[error("The deck has no {0}s")]
+    RankNotInDeck(crate::poker::card::Rank),
+    #[error("Expected {expected} cards, found {found}")]
+    WrongCardCount { expected: usize, found: usize },
+    #[error("{0} is not a percentage from 0% to 100%")]
+    InvalidRangePercent(String),
+    #[error("{0} does not have a weight from 0 to 1")]
+    InvalidRangeWeight(String),
+    #[error("Line {0} of the showdown is malformed")]
+    MalformedShowdownLine(usize),
+    #[error("{0} is already in the deck")]
+    DuplicateCardInDeck(Card),
     #[error("Error reading characters while parsing")]
     UnexpectedCardChar,
     #[error("Hand must contain exactly 2 cards")]
     InvalidHandSize,
     #[error("Holdem hands should never have more than 7 cards in them.")]
     HoldemHandSize,
-    // #[error("Card already added to hand {0}")]
-    // DuplicateCardInHand(Card),
-    // #[error("Extra un-used characters found after parsing")]
-    // UnparsedCharsRemaining,
+    #[error("Holdem hand must have exactly 7 cards to split into hole and board.")]
+    IncompleteHoldemHand,
+    #[error("Card already added to hand {0}")]
+    DuplicateCardInHand(Card),
+    #[error("Extra un-used characters found after parsing")]
+    UnparsedCharsRemaining,
     #[error("Hand range can't be offsuit while cards are suiterd")]
     OffSuitWithMatchingSuit,
     #[error("Hand range is suited while cards are not.")]
@@ -25,8 +47,18 @@ pub enum Error {
     InvalidPlusModifier,
     #[error("The gap between cards must be constant when defining a hand range.")]
     InvalidGap,
-    #[error("Pairs can't be suited.")]
+    #[error("Pairs can't be suited or offsuit.")]
     InvalidSuitedPairs,
+    #[error("A board can't have more than 5 cards.")]
+    InvalidBoardSize,
+    #[error("No hand in the range can be dealt alongside the known cards.")]
+    EmptyRange,
+    #[error("Card {0} is listed more than once among the dead cards.")]
+    DuplicateCardInRange(Card),
+    #[error("Not enough cards left in the deck.")]
+    NotEnoughCards,
+    #[error("{0:?} hands can't be ranked as a high hand")]
+    UnsupportedVariant(crate::poker::variant::GameVariant),
 }
 
 pub type Result<T, E = Error> = core::result::Result<T, E>;
@@ -0,0 +1,255 @@
+use std::collections::HashSet;
+
+use crate::{
+    error::{Error, Result},
+    poker::{
+        card::{self, Card},
+        deck::Deck,
+        hand::Hand,
+        rank::{HandRanker, Rank, RankCategory},
+    },
+};
+
+/// The betting rounds of a Hold'em hand, named by the cards on the board.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Debug)]
+pub enum Street {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+}
+
+impl Street {
+    pub const fn all() -> [Self; 4] {
+        [Street::Preflop, Street::Flop, Street::Turn, Street::River]
+    }
+
+    /// Number of board cards dealt by this street.
+    pub fn board_size(&self) -> usize {
+        match self {
+            Street::Preflop => 0,
+            Street::Flop => 3,
+            Street::Turn => 4,
+            Street::River => 5,
+        }
+    }
+
+    /// The street a board of `size` cards is on, if any.
+    pub fn from_board_size(size: usize) -> Option<Self> {
+        Self::all().into_iter().find(|s| s.board_size() == size)
+    }
+}
+
+/// How the hole cards pair up with a flop, see `read_flop`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum PairType {
+    /// A pocket pair matching a flop rank.
+    Set,
+    /// A pocket pair above every flop card.
+    OverPair,
+    /// A hole card pairs the highest flop rank.
+    TopPair,
+    /// A hole card pairs a flop rank between the highest and lowest.
+    MiddlePair,
+    /// A hole card pairs the lowest flop rank.
+    BottomPair,
+    /// A pocket pair below the highest flop card that doesn't make a set.
+    UnderPair,
+    /// Nothing in the hole pairs.
+    NoPair,
+}
+
+/// What a flop gives a player, see `read_flop`.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct FlopRead {
+    /// The best hand of the hole cards and the flop.
+    pub made: Rank,
+    pub pair_type: PairType,
+    /// Whether the hole cards make a better category than the flop alone.
+    pub connects: bool,
+}
+
+/// Reads a flop for the holder of `hole`: the made hand, how the hole
+/// cards pair and whether they connect with the board at all. A hole card
+/// pairing the flop names the pair by the highest such card, unless both
+/// hole cards do and it's a set.
+pub fn read_flop(hole: [Card; 2], flop: [Card; 3]) -> FlopRead {
+    let made = [hole.as_slice(), &flop].concat().rank();
+    let connects = made.category() > flop.rank().category();
+
+    let mut flop_ranks: Vec<&card::Rank> = flop.iter().map(Card::rank).collect();
+    flop_ranks.sort_by(|a, b| b.cmp(a));
+    flop_ranks.dedup();
+    let mut hole_ranks = [hole[0].rank(), hole[1].rank()];
+    hole_ranks.sort_by(|a, b| b.cmp(a));
+
+    let paired = hole_ranks
+        .iter()
+        .find_map(|r| flop_ranks.iter().position(|f| f == r));
+    let pair_type = match paired {
+        Some(_) if hole_ranks[0] == hole_ranks[1] => PairType::Set,
+        Some(0) => PairType::TopPair,
+        Some(i) if i == flop_ranks.len() - 1 => PairType::BottomPair,
+        Some(_) => PairType::MiddlePair,
+        None if hole_ranks[0] != hole_ranks[1] => PairType::NoPair,
+        None if hole_ranks[0] > flop_ranks[0] => PairType::OverPair,
+        None => PairType::UnderPair,
+    };
+    FlopRead {
+        made,
+        pair_type,
+        connects,
+    }
+}
+
+/// The rank the board makes on its own, which every player still in the
+/// hand shares. Only a complete 5 card board has one.
+pub fn board_plays(board: &[Card]) -> Option<Rank> {
+    if board.len() != 5 {
+        return None;
+    }
+    Some(Hand::new_with_cards(board.to_vec()).rank_five())
+}
+
+/// Every category the hole cards can finish as once `remaining_streets`
+/// more streets are dealt, e.g. `2` on the flop to see the turn and river.
+/// `0` gives just the current category.
+///
+/// # Errors
+///
+/// * `Error::InvalidBoardSize` - If the board isn't empty, a flop, turn or
+///   river, or there aren't that many streets left to deal.
+/// * `Error::DuplicateCardInHand` - If a card is repeated.
+pub fn reachable_categories(
+    hole: &[Card; 2],
+    board: &[Card],
+    remaining_streets: usize,
+) -> Result<HashSet<RankCategory>> {
+    let current = Street::from_board_size(board.len()).ok_or(Error::InvalidBoardSize)?;
+    let target = Street::all()
+        .into_iter()
+        .filter(|s| *s >= current)
+        .nth(remaining_streets)
+        .ok_or(Error::InvalidBoardSize)?
+        .board_size();
+    let hand = Hand::new_with_cards(hole.to_vec()).with_added(board)?;
+    let remaining = Deck::standard().without(hand.cards()).get_all_cards();
+
+    let mut categories = HashSet::new();
+    let to_come = target - board.len();
+    let mut picked: Vec<usize> = (0..to_come).collect();
+    loop {
+        let runout: Vec<Card> = picked.iter().map(|&i| remaining[i].clone()).collect();
+        categories.insert(hand.with_added(&runout)?.rank().category());
+
+        // Step to the next combination of `to_come` remaining cards.
+        let Some(i) = (0..to_come).rfind(|&i| picked[i] < remaining.len() - to_come + i) else {
+            break;
+        };
+        picked[i] += 1;
+        for j in i + 1..to_come {
+            picked[j] = picked[j - 1] + 1;
+        }
+    }
+    Ok(categories)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_straight_board() {
+        let board = Hand::new_from_strs(&["s5", "h6", "d7", "c8", "s9"]).unwrap();
+        assert_eq!(Some(Rank::Straight(4)), board_plays(board.cards()));
+    }
+
+    #[test]
+    fn test_flush_board() {
+        let board = Hand::new_from_strs(&["h2", "h6", "h9", "hj", "hk"]).unwrap();
+        assert!(board_plays(board.cards()).unwrap().is_flush());
+    }
+
+    #[test]
+    fn test_incomplete_board() {
+        let board = Hand::new_from_strs(&["s5", "h6", "d7"]).unwrap();
+        assert_eq!(None, board_plays(board.cards()));
+    }
+
+    #[test]
+    fn test_reachable_categories() {
+        let hole = Hand::new_from_strs(&["h9", "h8"]).unwrap();
+        let hole = [hole.cards()[0].clone(), hole.cards()[1].clone()];
+        let flop = Hand::new_from_strs(&["hk", "h2", "c5"]).unwrap();
+
+        let reachable = reachable_categories(&hole, flop.cards(), 2).unwrap();
+        assert!(reachable.contains(&RankCategory::Flush));
+        assert!(reachable.contains(&RankCategory::TwoPair));
+        assert!(!reachable.contains(&RankCategory::FourOfAKind));
+        assert!(!reachable.contains(&RankCategory::FullHouse));
+
+        let turn = reachable_categories(&hole, flop.cards(), 1).unwrap();
+        assert!(turn.contains(&RankCategory::Flush));
+        assert!(!turn.contains(&RankCategory::TwoPair));
+
+        let now = reachable_categories(&hole, flop.cards(), 0).unwrap();
+        assert_eq!(HashSet::from([RankCategory::HighCard]), now);
+
+        let result = reachable_categories(&hole, flop.cards(), 3);
+        assert!(matches!(result, Err(Error::InvalidBoardSize)));
+        let result = reachable_categories(&hole, &flop.cards()[..2], 1);
+        assert!(matches!(result, Err(Error::InvalidBoardSize)));
+    }
+
+    #[test]
+    fn test_street_board_sizes() {
+        for street in Street::all() {
+            assert_eq!(Some(street), Street::from_board_size(street.board_size()));
+        }
+        assert_eq!(Some(Street::Turn), Street::from_board_size(4));
+        assert_eq!(None, Street::from_board_size(2));
+        assert_eq!(None, Street::from_board_size(6));
+    }
+
+    #[test]
+    fn test_read_flop() {
+        let read = |hole: &str, flop: &str| {
+            let hole = Hand::from_mixed_notation(hole).unwrap();
+            let flop = Hand::from_mixed_notation(flop).unwrap();
+            read_flop(
+                hole.cards().to_vec().try_into().unwrap(),
+                flop.cards().to_vec().try_into().unwrap(),
+            )
+        };
+
+        let top_pair = read("As Kd", "Kc 7h 2s");
+        assert_eq!(PairType::TopPair, top_pair.pair_type);
+        assert!(top_pair.made.is_one_pair());
+        assert!(top_pair.connects);
+
+        let overpair = read("Qs Qh", "Jc 7h 2s");
+        assert_eq!(PairType::OverPair, overpair.pair_type);
+        assert!(overpair.made.is_one_pair());
+        assert!(overpair.connects);
+
+        let air = read("As Kd", "9c 7h 2s");
+        assert_eq!(PairType::NoPair, air.pair_type);
+        assert!(air.made.is_high_card());
+        assert!(!air.connects);
+
+        assert_eq!(PairType::MiddlePair, read("7s 6d", "Kc 7h 2s").pair_type);
+        assert_eq!(PairType::BottomPair, read("As 2d", "Kc 7h 2s").pair_type);
+        assert_eq!(PairType::UnderPair, read("5s 5d", "Kc 7h 2s").pair_type);
+
+        for hole in ["Ks Kd", "7s 7d", "2c 2d"] {
+            let set = read(hole, "Kc 7h 2s");
+            assert_eq!(PairType::Set, set.pair_type);
+            assert!(set.made.is_three_of_a_kind());
+        }
+
+        let paired_board = read("As Qd", "Kc Kh 2s");
+        assert_eq!(PairType::NoPair, paired_board.pair_type);
+        assert!(paired_board.made.is_one_pair());
+        assert!(!paired_board.connects);
+    }
+}
@@ -0,0 +1,131 @@
+//! # Preflop Charts
+//!
+//! Opening ranges per table position, written in the same range notation
+//! accepted by `calculate_range_percent`.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use poker_rs::holdem::chart::{Position, PreflopChart};
+//! use poker_rs::poker::hand::Hand;
+//!
+//! let chart = PreflopChart::from_ranges(&[
+//!     (Position::Utg, "77+, AJs+, KQs, AQo+"),
+//!     (Position::Btn, "22+, A2+, K9+, QT+, JT"),
+//! ])
+//! .unwrap();
+//!
+//! let hand = Hand::new_from_strs(&["sa", "sk"]).unwrap();
+//! assert!(chart.should_open(Position::Utg, &hand).unwrap());
+//! ```
+
+use std::collections::HashMap;
+
+use crate::{
+    error::Result,
+    holdem::evaluator::{
+        hand_type::HandEvaluator,
+        range::{parse_range, Combinations},
+    },
+    poker::hand::Hand,
+};
+
+/// Seats at a six-handed table.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub enum Position {
+    Utg,
+    Mp,
+    Co,
+    Btn,
+    Sb,
+    Bb,
+}
+
+/// Opening ranges keyed by position.
+#[derive(Default)]
+pub struct PreflopChart {
+    ranges: HashMap<Position, Combinations>,
+}
+
+impl PreflopChart {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a chart from `(position, range)` pairs.
+    pub fn from_ranges(ranges: &[(Position, &str)]) -> Result<Self> {
+        let mut chart = Self::new();
+        for (position, range) in ranges {
+            chart.set_range(*position, range)?;
+        }
+        Ok(chart)
+    }
+
+    /// Sets the opening range for a position, replacing any previous one.
+    /// Classes may name either rank first, so `KAs` is the same as `AKs`.
+    pub fn set_range(&mut self, position: Position, range: &str) -> Result<&mut Self> {
+        self.ranges.insert(position, parse_range(range)?);
+        Ok(self)
+    }
+
+    /// Whether the two card hand is in the opening range of the position.
+    /// Positions without a range never open.
+    pub fn should_open(&self, position: Position, hand: &Hand) -> Result<bool> {
+        let class = hand.preflop_class()?;
+        match self.ranges.get(&position) {
+            Some(range) => range.contains_class(&class),
+            None => Ok(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chart() -> PreflopChart {
+        PreflopChart::from_ranges(&[
+            (Position::Utg, "77+, AJs+, KQs, AQo+"),
+            (Position::Btn, "22+, A2+, K9+, QT+, JT"),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_should_open() {
+        let chart = chart();
+        let aks = Hand::new_from_strs(&["sa", "sk"]).unwrap();
+        assert!(chart.should_open(Position::Utg, &aks).unwrap());
+
+        let kas = Hand::new_from_strs(&["hk", "ha"]).unwrap();
+        assert!(chart.should_open(Position::Utg, &kas).unwrap());
+
+        let a5o = Hand::new_from_strs(&["sa", "h5"]).unwrap();
+        assert!(!chart.should_open(Position::Utg, &a5o).unwrap());
+        assert!(chart.should_open(Position::Btn, &a5o).unwrap());
+
+        let sevens = Hand::new_from_strs(&["s7", "h7"]).unwrap();
+        assert!(chart.should_open(Position::Utg, &sevens).unwrap());
+        assert!(!chart.should_open(Position::Bb, &sevens).unwrap());
+    }
+
+    #[test]
+    fn test_low_first_range() {
+        let chart = PreflopChart::from_ranges(&[(Position::Co, "KAs, 9T+")]).unwrap();
+        let aks = Hand::new_from_strs(&["sa", "sk"]).unwrap();
+        assert!(chart.should_open(Position::Co, &aks).unwrap());
+        let t9o = Hand::new_from_strs(&["h9", "st"]).unwrap();
+        assert!(chart.should_open(Position::Co, &t9o).unwrap());
+        let ako = Hand::new_from_strs(&["sa", "hk"]).unwrap();
+        assert!(!chart.should_open(Position::Co, &ako).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(PreflopChart::from_ranges(&[(Position::Co, "AKx")]).is_err());
+
+        let hand = Hand::new_from_strs(&["sa", "sk", "sq"]).unwrap();
+        assert!(chart().should_open(Position::Utg, &hand).is_err());
+    }
+}
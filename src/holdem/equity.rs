@@ -0,0 +1,552 @@
+//! # Range Equity
+//!
+//! Monte Carlo estimation of how often one hand range beats another in
+//! Texas Hold'em, or how known hands fare at a multiway table. Heads-up
+//! preflop all-ins can also be enumerated exactly.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use poker_rs::holdem::equity::range_vs_range;
+//! use rand::{rngs::StdRng, SeedableRng};
+//!
+//! let mut rng = StdRng::seed_from_u64(7);
+//! let equity = range_vs_range("QQ+", "76s, 65s", &[], 1000, &mut rng).unwrap();
+//! println!("QQ+ equity: {:.2}%", equity.equity() * 100.0);
+//! ```
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    error::{Error, Result},
+    holdem::{board::Street, evaluator::range::live_range_combos},
+    poker::{
+        card::Card,
+        deck::Deck,
+        hand::Hand,
+        rank::{compare_ranks, HandRanker, Rank},
+    },
+};
+
+/// Number of cards on a complete board.
+const BOARD_SIZE: usize = 5;
+
+/// How many times to redraw a pair of combos that share a card before
+/// giving up.
+const MAX_RESAMPLES: usize = 1000;
+
+/// Outcome tallies from the point of view of one range or hand.
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct Equity {
+    wins: usize,
+    ties: usize,
+    losses: usize,
+    /// Sum of the pot shares won in ties, 1/n for an n-way tie.
+    tie_shares: f64,
+}
+
+impl Equity {
+    pub fn wins(&self) -> usize {
+        self.wins
+    }
+
+    pub fn ties(&self) -> usize {
+        self.ties
+    }
+
+    pub fn losses(&self) -> usize {
+        self.losses
+    }
+
+    pub fn total(&self) -> usize {
+        self.wins + self.ties + self.losses
+    }
+
+    /// Share of the pot won on average, splitting ties between the tied
+    /// players.
+    pub fn equity(&self) -> f32 {
+        if self.total() == 0 {
+            return 0.0;
+        }
+        ((self.wins as f64 + self.tie_shares) / self.total() as f64) as f32
+    }
+
+    /// Tallies one heads-up result, `Greater` being a win.
+    fn record(&mut self, ordering: std::cmp::Ordering) {
+        match ordering {
+            std::cmp::Ordering::Greater => self.wins += 1,
+            std::cmp::Ordering::Equal => self.add_tie(2),
+            std::cmp::Ordering::Less => self.losses += 1,
+        }
+    }
+
+    fn add_tie(&mut self, players: usize) {
+        self.ties += 1;
+        self.tie_shares += 1.0 / players as f64;
+    }
+}
+
+/// Estimates the equity of range `a` against range `b`.
+///
+/// Each iteration samples a combo from each range that doesn't conflict
+/// with the board or the other combo, deals out the rest of the board and
+/// compares the best hands.
+///
+/// # Errors
+///
+/// * `Error::UnexpectedCardChar` - If either range can't be parsed.
+/// * `Error::InvalidBoardSize` - If the board has more than 5 cards.
+/// * `Error::EmptyRange` - If no combo pair can be dealt with the board.
+/// * `Error::DuplicateCardInRange` - If the board repeats a card.
+pub fn range_vs_range(
+    a: &str,
+    b: &str,
+    board: &[Card],
+    iterations: usize,
+    rng: &mut impl Rng,
+) -> Result<Equity> {
+    if board.len() > BOARD_SIZE {
+        return Err(Error::InvalidBoardSize);
+    }
+
+    let combos_a = live_range_combos(a, board)?;
+    let combos_b = live_range_combos(b, board)?;
+    let mut equity = Equity::default();
+
+    for _ in 0..iterations {
+        let (hole_a, hole_b) = sample_pair(&combos_a, &combos_b, rng)?;
+
+        let mut dead = board.to_vec();
+        dead.extend_from_slice(hole_a);
+        dead.extend_from_slice(hole_b);
+        let remaining = Deck::default().without(&dead).get_all_cards();
+
+        let mut full_board = board.to_vec();
+        full_board.extend(
+            remaining
+                .choose_multiple(rng, BOARD_SIZE - board.len())
+                .cloned(),
+        );
+
+        let rank_a = rank_with_board(hole_a, &full_board);
+        let rank_b = rank_with_board(hole_b, &full_board);
+        equity.record(rank_a.cmp(&rank_b));
+    }
+
+    Ok(equity)
+}
+
+/// Estimates the equity of each of several known hands, for any number of
+/// players. Ties split the pot between every tied player, so the equities
+/// sum to 1.
+///
+/// # Errors
+///
+/// * `Error::InvalidBoardSize` - If the board has more than 5 cards.
+/// * `Error::DuplicateCardInHand` - If a card is in two hands or a hand and
+///   the board.
+/// * `Error::NotEnoughCards` - If there are too many players to deal the
+///   board.
+pub fn multiway_equity(
+    hands: &[[Card; 2]],
+    board: &[Card],
+    iterations: usize,
+    rng: &mut impl Rng,
+) -> Result<Vec<Equity>> {
+    if board.len() > BOARD_SIZE {
+        return Err(Error::InvalidBoardSize);
+    }
+
+    let mut dead = board.to_vec();
+    for c in hands.iter().flatten() {
+        if dead.contains(c) {
+            return Err(Error::DuplicateCardInHand(c.clone()));
+        }
+        dead.push(c.clone());
+    }
+    let remaining = Deck::default().without(&dead).get_all_cards();
+    if remaining.len() < BOARD_SIZE - board.len() {
+        return Err(Error::NotEnoughCards);
+    }
+
+    let mut equities = vec![Equity::default(); hands.len()];
+    for _ in 0..iterations {
+        let mut full_board = board.to_vec();
+        full_board.extend(
+            remaining
+                .choose_multiple(rng, BOARD_SIZE - board.len())
+                .cloned(),
+        );
+
+        let ranks: Vec<Rank> = hands
+            .iter()
+            .map(|hole| rank_with_board(hole, &full_board))
+            .collect();
+        let winners = compare_ranks(&ranks);
+        for (i, equity) in equities.iter_mut().enumerate() {
+            if !winners.contains(&i) {
+                equity.losses += 1;
+            } else if winners.len() == 1 {
+                equity.wins += 1;
+            } else {
+                equity.add_tie(winners.len());
+            }
+        }
+    }
+
+    Ok(equities)
+}
+
+/// Follows one random runout from the current street to the river,
+/// estimating the hero's equity on each street with `iterations` deals
+/// of the rest of the board. On the river the result is known, so every
+/// deal agrees.
+///
+/// # Errors
+///
+/// * `Error::InvalidBoardSize` - If the board isn't empty, a flop, turn or
+///   river.
+/// * `Error::DuplicateCardInHand` - If a card is in both hands or a hand
+///   and the board.
+pub fn equity_by_street(
+    hero: &[Card; 2],
+    villain: &[Card; 2],
+    board: &[Card],
+    iterations: usize,
+    rng: &mut impl Rng,
+) -> Result<Vec<(Street, Equity)>> {
+    let current = Street::from_board_size(board.len()).ok_or(Error::InvalidBoardSize)?;
+    let hands = [hero.clone(), villain.clone()];
+    let dead = [hero.as_slice(), villain, board].concat();
+    let mut runout = board.to_vec();
+    runout.extend(
+        Deck::standard()
+            .without(&dead)
+            .choose_multiple(BOARD_SIZE - board.len(), rng),
+    );
+    Street::all()
+        .into_iter()
+        .filter(|s| *s >= current)
+        .map(|street| {
+            let board = &runout[..street.board_size()];
+            Ok((
+                street,
+                multiway_equity(&hands, board, iterations, rng)?[0].clone(),
+            ))
+        })
+        .collect()
+}
+
+/// Runs the rest of the board out twice, as when players agree to "run it
+/// twice", returning the hero's result on each runout. The second runout
+/// is dealt from the cards left after the first, like at a live table.
+///
+/// # Errors
+///
+/// * `Error::InvalidBoardSize` - If the board has more than 5 cards.
+/// * `Error::NotEnoughCards` - If the deck can't deal both runouts.
+pub fn run_it_twice(
+    hero: &[Card; 2],
+    villain: &[Card; 2],
+    board: &[Card],
+    deck: &Deck,
+    rng: &mut impl Rng,
+) -> Result<(Equity, Equity)> {
+    let (first, second) = deal_runouts(hero, villain, board, deck, rng)?;
+    let result = |runout: &[Card]| {
+        let mut equity = Equity::default();
+        equity.record(rank_with_board(hero, runout).cmp(&rank_with_board(villain, runout)));
+        equity
+    };
+    Ok((result(&first), result(&second)))
+}
+
+/// Deals two complete boards from `deck` that share only the cards of
+/// `board`.
+fn deal_runouts(
+    hero: &[Card; 2],
+    villain: &[Card; 2],
+    board: &[Card],
+    deck: &Deck,
+    rng: &mut impl Rng,
+) -> Result<(Vec<Card>, Vec<Card>)> {
+    if board.len() > BOARD_SIZE {
+        return Err(Error::InvalidBoardSize);
+    }
+    let to_come = BOARD_SIZE - board.len();
+    let dead = [hero.as_slice(), villain, board].concat();
+    let mut remaining = deck.without(&dead).get_all_cards();
+    if remaining.len() < 2 * to_come {
+        return Err(Error::NotEnoughCards);
+    }
+
+    remaining.shuffle(rng);
+    let mut first = board.to_vec();
+    first.extend_from_slice(&remaining[..to_come]);
+    let mut second = board.to_vec();
+    second.extend_from_slice(&remaining[to_come..2 * to_come]);
+    Ok((first, second))
+}
+
+/// Exact heads-up all-in equity before the flop, from the hero's point of
+/// view, found by dealing every board from the 48 unseen cards.
+///
+/// That is C(48, 5) = 1,712,304 boards, so expect this to take on the
+/// order of a second in a release build and much longer in debug.
+///
+/// # Panics
+///
+/// If a card is repeated within or between the two hands.
+pub fn preflop_allin_equity(hero: [Card; 2], villain: [Card; 2]) -> Equity {
+    let cards = [hero.as_slice(), &villain].concat();
+    assert!(
+        (0..cards.len()).all(|i| !cards[i + 1..].contains(&cards[i])),
+        "preflop_allin_equity needs four different cards"
+    );
+    let remaining = Deck::standard().without(&cards).get_all_cards();
+    let n = remaining.len();
+    let mut equity = Equity::default();
+    for a in 0..n {
+        for b in a + 1..n {
+            for c in b + 1..n {
+                for d in c + 1..n {
+                    for e in d + 1..n {
+                        let board = [a, b, c, d, e].map(|i| remaining[i].clone());
+                        let rank = |hole: &[Card; 2]| {
+                            let [x, y] = hole.clone();
+                            let [b0, b1, b2, b3, b4] = board.clone();
+                            [x, y, b0, b1, b2, b3, b4].rank()
+                        };
+                        equity.record(rank(&hero).cmp(&rank(&villain)));
+                    }
+                }
+            }
+        }
+    }
+    equity
+}
+
+fn sample_pair<'a>(
+    combos_a: &'a [[Card; 2]],
+    combos_b: &'a [[Card; 2]],
+    rng: &mut impl Rng,
+) -> Result<(&'a [Card; 2], &'a [Card; 2])> {
+    for _ in 0..MAX_RESAMPLES {
+        let hole_a = combos_a.choose(rng).ok_or(Error::EmptyRange)?;
+        let hole_b = combos_b.choose(rng).ok_or(Error::EmptyRange)?;
+        if !hole_a.iter().any(|c| hole_b.contains(c)) {
+            return Ok((hole_a, hole_b));
+        }
+    }
+    Err(Error::EmptyRange)
+}
+
+fn rank_with_board(hole: &[Card; 2], board: &[Card]) -> Rank {
+    let mut cards = hole.to_vec();
+    cards.extend_from_slice(board);
+    Hand::new_with_cards(cards).rank()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_value_vs_draws() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let equity = range_vs_range("QQ+", "76s, 65s, 87s", &[], 2000, &mut rng).unwrap();
+        assert_eq!(2000, equity.total());
+        assert!(
+            equity.equity() > 0.7 && equity.equity() < 0.9,
+            "Unexpected equity {:.3}",
+            equity.equity()
+        );
+    }
+
+    #[test]
+    fn test_deterministic_with_seed() {
+        let run = || {
+            let mut rng = StdRng::seed_from_u64(1);
+            range_vs_range("AK", "22+", &[], 200, &mut rng).unwrap()
+        };
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn test_board_blockers() {
+        let board = Hand::new_from_strs(&["sa", "ha", "da"]).unwrap();
+        let mut rng = StdRng::seed_from_u64(3);
+        let result = range_vs_range("AA", "KK", board.cards(), 10, &mut rng);
+        assert!(matches!(result, Err(Error::EmptyRange)));
+
+        let board = Hand::new_from_strs(&["sk", "h2", "sk"]).unwrap();
+        let result = range_vs_range("AA", "QQ", board.cards(), 10, &mut rng);
+        assert!(matches!(result, Err(Error::DuplicateCardInRange(_))));
+
+        let board = Hand::new_from_strs(&["sa", "ha", "d2", "c3", "s4", "h5"]).unwrap();
+        let result = range_vs_range("KK", "QQ", board.cards(), 10, &mut rng);
+        assert!(matches!(result, Err(Error::InvalidBoardSize)));
+    }
+
+    #[test]
+    fn test_board_conflicts_filtered() {
+        // Only the AcAd combo is live, and it has quads against any kings.
+        let board = Hand::new_from_strs(&["sa", "ha", "h7"]).unwrap();
+        let mut rng = StdRng::seed_from_u64(455);
+        let equity = range_vs_range("AA", "KK", board.cards(), 200, &mut rng).unwrap();
+        assert_eq!(200, equity.wins());
+    }
+
+    #[test]
+    fn test_conflicting_combos_resampled() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let equity = range_vs_range("AA", "AA", &[], 100, &mut rng).unwrap();
+        assert_eq!(100, equity.total());
+    }
+
+    #[test]
+    fn test_multiway_overpairs() {
+        let hand = |a: &str, b: &str| {
+            [
+                Card::try_from_str(a).unwrap(),
+                Card::try_from_str(b).unwrap(),
+            ]
+        };
+        let hands = [hand("sa", "ha"), hand("sk", "hk"), hand("sq", "hq")];
+        let mut rng = StdRng::seed_from_u64(435);
+        let equities = multiway_equity(&hands, &[], 4000, &mut rng).unwrap();
+
+        // Published preflop equities: AA 66.2%, KK 18.3%, QQ 15.5%.
+        let expected = [0.662, 0.183, 0.155];
+        for (equity, expected) in equities.iter().zip(expected) {
+            assert_eq!(4000, equity.total());
+            assert!(
+                (equity.equity() - expected).abs() < 0.03,
+                "Unexpected equity {:.3}",
+                equity.equity()
+            );
+        }
+        let sum: f32 = equities.iter().map(Equity::equity).sum();
+        assert!((sum - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_multiway_split_and_errors() {
+        let board = Hand::new_from_strs(&["sa", "sk", "sq", "sj", "st"]).unwrap();
+        let hands: Vec<[Card; 2]> = Hand::new_from_strs(&["h2", "h3", "d2", "d3", "c2", "c3"])
+            .unwrap()
+            .cards()
+            .chunks(2)
+            .map(|c| [c[0].clone(), c[1].clone()])
+            .collect();
+        let mut rng = StdRng::seed_from_u64(1);
+        let equities = multiway_equity(&hands, board.cards(), 10, &mut rng).unwrap();
+        for equity in &equities {
+            assert_eq!(10, equity.ties());
+            assert!((equity.equity() - 1.0 / 3.0).abs() < 1e-6);
+        }
+
+        let result = multiway_equity(&hands[..1], &hands[0], 10, &mut rng);
+        assert!(matches!(result, Err(Error::DuplicateCardInHand(_))));
+    }
+
+    #[test]
+    fn test_run_it_twice() {
+        let cards = |s: &[&str]| Hand::new_from_strs(s).unwrap().cards().to_vec();
+        let hero = [cards(&["sa"])[0].clone(), cards(&["ha"])[0].clone()];
+        let villain = [cards(&["sk"])[0].clone(), cards(&["hk"])[0].clone()];
+        let board = cards(&["da", "d7", "c2"]);
+        let deck = Deck::standard();
+
+        let mut rng = StdRng::seed_from_u64(445);
+        let (first, second) = deal_runouts(&hero, &villain, &board, &deck, &mut rng).unwrap();
+        assert_eq!(board, first[..3]);
+        assert_eq!(board, second[..3]);
+        assert_ne!(first, second);
+        assert!(!first[3..].iter().any(|c| second[3..].contains(c)));
+
+        let (run1, run2) = run_it_twice(&hero, &villain, &board, &deck, &mut rng).unwrap();
+        assert_eq!(1, run1.total());
+        assert_eq!(1, run2.total());
+
+        // Quad aces can't be outdrawn in two cards.
+        let quads = cards(&["da", "ca", "d7"]);
+        let (run1, run2) = run_it_twice(&hero, &villain, &quads, &deck, &mut rng).unwrap();
+        assert_eq!(1, run1.wins());
+        assert_eq!(1, run2.wins());
+
+        let full_board = cards(&["da", "d7", "c2", "c3", "c4", "c5"]);
+        let result = run_it_twice(&hero, &villain, &full_board, &deck, &mut rng);
+        assert!(matches!(result, Err(Error::InvalidBoardSize)));
+    }
+
+    #[test]
+    fn test_preflop_allin_equity() {
+        let hole = |a: &str, b: &str| {
+            [
+                Card::try_from_str(a).unwrap(),
+                Card::try_from_str(b).unwrap(),
+            ]
+        };
+        let equity = preflop_allin_equity(hole("sa", "ha"), hole("sk", "dk"));
+        assert_eq!(1_712_304, equity.total());
+        // Published exact figure with one shared suit: AA 81.9%, KK 18.1%.
+        assert!(
+            (equity.equity() - 0.819).abs() < 0.001,
+            "Unexpected equity {:.4}",
+            equity.equity()
+        );
+    }
+
+    #[test]
+    fn test_equity_by_street() {
+        let cards = |s: &str| Hand::from_mixed_notation(s).unwrap().cards().to_vec();
+        let hole = |s: &str| -> [Card; 2] { cards(s).try_into().unwrap() };
+        let mut rng = StdRng::seed_from_u64(489);
+
+        let streets = equity_by_street(&hole("As Ah"), &hole("Ks Kh"), &[], 50, &mut rng).unwrap();
+        let names: Vec<Street> = streets.iter().map(|(s, _)| *s).collect();
+        assert_eq!(Street::all().to_vec(), names);
+        assert!(streets.iter().all(|(_, e)| e.total() == 50));
+
+        // Aces win on this river every time.
+        let river = cards("Ad 7c 2s 9h Jd");
+        let streets =
+            equity_by_street(&hole("As Ah"), &hole("Ks Kh"), &river, 20, &mut rng).unwrap();
+        assert_eq!(1, streets.len());
+        assert_eq!((Street::River, 1.0), (streets[0].0, streets[0].1.equity()));
+        assert_eq!(20, streets[0].1.wins());
+
+        // The board plays for both.
+        let river = cards("Ad Kd Qd Jd Td");
+        let streets =
+            equity_by_street(&hole("2s 3h"), &hole("4s 5h"), &river, 20, &mut rng).unwrap();
+        assert_eq!(0.5, streets[0].1.equity());
+        assert_eq!(20, streets[0].1.ties());
+
+        let turn = cards("Ad 7c 2s 9h");
+        let streets =
+            equity_by_street(&hole("As Ah"), &hole("Ks Kh"), &turn, 20, &mut rng).unwrap();
+        assert_eq!(
+            vec![Street::Turn, Street::River],
+            streets.iter().map(|(s, _)| *s).collect::<Vec<_>>()
+        );
+        let river = &streets[1].1;
+        assert!(river.wins() == 20 || river.losses() == 20 || river.ties() == 20);
+
+        let result = equity_by_street(&hole("As Ah"), &hole("Ks Kh"), &turn[..2], 20, &mut rng);
+        assert!(matches!(result, Err(Error::InvalidBoardSize)));
+        let result = equity_by_street(&hole("As Ah"), &hole("As Kh"), &[], 20, &mut rng);
+        assert!(matches!(result, Err(Error::DuplicateCardInHand(_))));
+    }
+
+    #[test]
+    #[should_panic(expected = "four different cards")]
+    fn test_preflop_allin_equity_shared_card() {
+        let ace = Card::try_from_str("sa").unwrap();
+        let king = Card::try_from_str("hk").unwrap();
+        let queen = Card::try_from_str("dq").unwrap();
+        preflop_allin_equity([ace.clone(), king], [ace, queen]);
+    }
+}
@@ -10,6 +10,20 @@ pub enum HandType {
     UnPaired,
 }
 
+impl HandType {
+    pub fn is_suited(&self) -> bool {
+        matches!(self, HandType::Suited)
+    }
+
+    pub fn is_offsuit(&self) -> bool {
+        matches!(self, HandType::Offsuit)
+    }
+
+    pub fn is_paired(&self) -> bool {
+        matches!(self, HandType::Paired)
+    }
+}
+
 pub trait HandEvaluator {
     fn cards(&self) -> &[Card];
 
@@ -32,12 +46,38 @@ pub trait HandEvaluator {
 
         Ok(HandType::Offsuit)
     }
+
+    /// Like `evaluate`, but also returns the gap between the two ranks,
+    /// e.g. `(Suited, 1)` for suited connectors and `(Paired, 0)` for pairs.
+    fn evaluate_full(&self) -> Result<(HandType, i8)> {
+        let hand_type = self.evaluate()?;
+        let cards = self.cards();
+        Ok((hand_type, cards[0].rank().gap(cards[1].rank())))
+    }
+
+    /// The preflop hand class in range notation with the higher rank
+    /// first, e.g. `AKs`, `T9o` or `QQ`.
+    fn preflop_class(&self) -> Result<String> {
+        let hand_type = self.evaluate()?;
+        let cards = self.cards();
+        let (high, low) = if cards[0].rank() >= cards[1].rank() {
+            (cards[0].rank(), cards[1].rank())
+        } else {
+            (cards[1].rank(), cards[0].rank())
+        };
+        let class = match hand_type {
+            HandType::Suited => format!("{}{}s", high, low),
+            HandType::Offsuit => format!("{}{}o", high, low),
+            _ => format!("{}{}", high, low),
+        };
+        Ok(class)
+    }
 }
 
 /// Implementation for `Hand`
 impl HandEvaluator for Hand {
     fn cards(&self) -> &[Card] {
-        &self.cards()
+        self.cards()
     }
 }
 
@@ -65,4 +105,52 @@ mod tests {
         let t = hand1.evaluate().unwrap();
         assert_eq!(HandType::Paired, t)
     }
+
+    #[test]
+    fn test_preflop_class() {
+        let hand = Hand::new_from_strs(&["s3", "st"]).unwrap();
+        assert_eq!("T3s", hand.preflop_class().unwrap());
+        let hand = Hand::new_from_strs(&["ha", "dk"]).unwrap();
+        assert_eq!("AKo", hand.preflop_class().unwrap());
+        let hand = Hand::new_from_strs(&["h7", "d7"]).unwrap();
+        assert_eq!("77", hand.preflop_class().unwrap());
+        let hand = Hand::new_from_strs(&["h7"]).unwrap();
+        assert!(hand.preflop_class().is_err());
+    }
+
+    #[test]
+    fn test_evaluate_full() {
+        let pair = Hand::new_from_strs(&["d8", "s8"]).unwrap();
+        assert_eq!((HandType::Paired, 0), pair.evaluate_full().unwrap());
+
+        let connector = Hand::new_from_strs(&["s9", "st"]).unwrap();
+        assert_eq!((HandType::Suited, 1), connector.evaluate_full().unwrap());
+
+        let gapper = Hand::new_from_strs(&["hj", "s9"]).unwrap();
+        assert_eq!((HandType::Offsuit, 2), gapper.evaluate_full().unwrap());
+
+        let wide = Hand::new_from_strs(&["ha", "h2"]).unwrap();
+        assert_eq!((HandType::Suited, 12), wide.evaluate_full().unwrap());
+
+        let too_many = Hand::new_from_strs(&["ha", "h2", "h3"]).unwrap();
+        assert!(too_many.evaluate_full().is_err());
+    }
+
+    #[test]
+    fn test_predicates() {
+        let cases = [
+            (HandType::Suited, [true, false, false]),
+            (HandType::Offsuit, [false, true, false]),
+            (HandType::Paired, [false, false, true]),
+            (HandType::UnPaired, [false, false, false]),
+        ];
+        for (hand_type, expected) in cases {
+            let actual = [
+                hand_type.is_suited(),
+                hand_type.is_offsuit(),
+                hand_type.is_paired(),
+            ];
+            assert_eq!(expected, actual, "{:?}", hand_type);
+        }
+    }
 }
@@ -1,2 +1,3 @@
 pub mod hand_type;
 pub mod range;
+pub mod strength;
@@ -16,6 +16,15 @@
 //! in double counting of combinations.The function includes a mechanism to detect and remove such duplicates,
 //! but it is best practice to input non-overlapping ranges to ensure accurate calculation.
 //!
+//! A `!` removes the hands that follow it from the range parsed so far, so
+//! `22+ !55` is every pair except fives.
+//!
+//! A percentage such as `20%` stands for the strongest 20% of starting
+//! hands, ordered by the Chen formula, and can be mixed with other hands.
+//!
+//! `weighted_combo_count` also accepts a frequency after each part, as in
+//! `AKs:0.5, QQ+`.
+//!
 //! //! ## Example
 //!
 //! ```rust
@@ -29,18 +38,22 @@
 //! }
 //! ```
 
-use std::collections::HashSet;
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt,
+};
 
 use crate::{
     error::{Error, Result},
-    poker::card::Rank,
+    poker::{
+        card::{Card, Rank, Suit},
+        variant::GameVariant,
+    },
 };
 use once_cell::sync::Lazy;
 use regex::Regex;
 
-use super::hand_type::HandType;
-
-const HAND_COMBINATIONS: f32 = 1326.0;
+use super::{hand_type::HandType, strength::top_percent_classes};
 
 // const OFF_SUIT_COMBINATIONS: u16 = 936;
 // const SUITED_COMBINATIONS: u16 = 312;
@@ -49,7 +62,7 @@ const HAND_COMBINATIONS: f32 = 1326.0;
 const SPEC_OFF_SUIT_COMBINATIONS: usize = 12;
 const SPEC_SUITED_COMBINATIONS: usize = 4;
 const SPEC_PAIRED_COMBINATIONS: usize = 6;
-// const SPEC_UNPAIRED_COMBINATIONS: usize = 16;
+const SPEC_UNPAIRED_COMBINATIONS: usize = 16;
 
 // const PAIRED_COUNT: u16 = 13;
 // const UNPAIRED_COUNT: u16 = 78;
@@ -58,7 +71,8 @@ const RANGE_PAT: &str = r"(?i)^(?:[AKQJTt2-9]{2}[os]?\+?)$";
 static RANGE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(RANGE_PAT).unwrap());
 static TRIM_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s*([,])\s*").unwrap());
 
-struct Combinations {
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Combinations {
     offsuit: HashSet<String>,
     suited: HashSet<String>,
     paired: HashSet<String>,
@@ -84,21 +98,228 @@ impl Combinations {
     pub fn len_of_paired(&self) -> usize {
         self.paired.len()
     }
+
+    pub fn combo_count(&self) -> usize {
+        self.len_of_offsuit() * SPEC_OFF_SUIT_COMBINATIONS
+            + self.len_of_suited() * SPEC_SUITED_COMBINATIONS
+            + self.len_of_paired() * SPEC_PAIRED_COMBINATIONS
+    }
+
+    /// Checks for a hand class written in range notation, e.g. `AKs` or `QQ`.
+    pub fn contains_class(&self, class: &str) -> Result<bool> {
+        let (rank1, rank2, hand_type) = parse_cards(class)?;
+        let key = format!("{}{}", rank1, rank2);
+        Ok(match hand_type {
+            HandType::Offsuit => self.offsuit.contains(&key),
+            HandType::Suited => self.suited.contains(&key),
+            HandType::Paired => self.paired.contains(&format!("{}", rank1)),
+            HandType::UnPaired => self.offsuit.contains(&key) && self.suited.contains(&key),
+        })
+    }
+}
+
+/// A mutable set of hand classes (e.g. `AA`, `AKs`, `T9o`), for building
+/// ranges interactively.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RangeSet {
+    combinations: Combinations,
+}
+
+impl RangeSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a set from a range string such as `"22+, AJo+, ATs+"`.
+    pub fn parse(s: &str) -> Result<Self> {
+        Ok(Self {
+            combinations: parse_range(s)?,
+        })
+    }
+
+    /// Adds a hand class. An unpaired class without a suffix, like `AK`,
+    /// adds both the suited and offsuit versions. Returns whether the set
+    /// changed.
+    pub fn insert(&mut self, class: &str) -> Result<bool> {
+        let (key, hand_type) = parse_single_class(class)?;
+        let c = &mut self.combinations;
+        let changed = match hand_type {
+            HandType::Offsuit => c.offsuit.insert(key),
+            HandType::Suited => c.suited.insert(key),
+            HandType::Paired => c.paired.insert(key),
+            HandType::UnPaired => c.offsuit.insert(key.clone()) | c.suited.insert(key),
+        };
+        Ok(changed)
+    }
+
+    /// Removes a hand class, returning whether the set changed.
+    pub fn remove(&mut self, class: &str) -> Result<bool> {
+        let (key, hand_type) = parse_single_class(class)?;
+        let c = &mut self.combinations;
+        let changed = match hand_type {
+            HandType::Offsuit => c.offsuit.remove(&key),
+            HandType::Suited => c.suited.remove(&key),
+            HandType::Paired => c.paired.remove(&key),
+            HandType::UnPaired => c.offsuit.remove(&key) | c.suited.remove(&key),
+        };
+        Ok(changed)
+    }
+
+    pub fn contains(&self, class: &str) -> Result<bool> {
+        let (key, hand_type) = parse_single_class(class)?;
+        let c = &self.combinations;
+        let contained = match hand_type {
+            HandType::Offsuit => c.offsuit.contains(&key),
+            HandType::Suited => c.suited.contains(&key),
+            HandType::Paired => c.paired.contains(&key),
+            HandType::UnPaired => c.offsuit.contains(&key) && c.suited.contains(&key),
+        };
+        Ok(contained)
+    }
+
+    pub fn combo_count(&self) -> usize {
+        self.combinations.combo_count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.combo_count() == 0
+    }
+}
+
+/// Lists the classes strongest first: pairs, then suited and then offsuit
+/// hands, e.g. `AA, KK, AKs, AKo`.
+impl fmt::Display for RangeSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sorted = |set: &HashSet<String>| {
+            let mut classes: Vec<(Rank, Rank)> = set.iter().map(|c| parse_class(c)).collect();
+            classes.sort_by(|a, b| b.cmp(a));
+            classes
+        };
+
+        let mut classes = Vec::new();
+        for (rank, _) in sorted(&self.combinations.paired) {
+            classes.push(format!("{}{}", rank, rank));
+        }
+        for (rank1, rank2) in sorted(&self.combinations.suited) {
+            classes.push(format!("{}{}s", rank1, rank2));
+        }
+        for (rank1, rank2) in sorted(&self.combinations.offsuit) {
+            classes.push(format!("{}{}o", rank1, rank2));
+        }
+        write!(f, "{}", classes.join(", "))
+    }
+}
+
+/// Range parsing for a deck with a chosen set of ranks, such as a short
+/// deck without the twos to fives, and a game's way of counting combos.
+/// The default uses all 13 ranks and Hold'em counting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeParser {
+    ranks: Vec<Rank>,
+    /// Number of distinct two card hands the deck can deal.
+    hand_combinations: usize,
+    variant: GameVariant,
+}
+
+impl Default for RangeParser {
+    fn default() -> Self {
+        Self::new(&Rank::ranks())
+    }
+}
+
+impl RangeParser {
+    pub fn new(ranks: &[Rank]) -> Self {
+        let cards = ranks.len() * Suit::suits().len();
+        Self {
+            ranks: ranks.to_vec(),
+            hand_combinations: cards * cards.saturating_sub(1) / 2,
+            variant: GameVariant::TexasHoldem,
+        }
+    }
+
+    /// The deck and combo counting of a game. A short deck drops the twos
+    /// to fives. Ace-to-five lowball ignores suits, since flushes don't
+    /// count against a low hand, so `A2s`, `A2o` and `A2` all stand for
+    /// the same 16 combos. Other games count like Hold'em.
+    ///
+    /// Only the counting changes: the range syntax is still read with the
+    /// ace high, so `+` in a lowball range runs up towards the ace as it
+    /// does in Hold'em rather than down towards better low hands.
+    pub fn for_variant(variant: GameVariant) -> Self {
+        let parser = match variant {
+            GameVariant::ShortDeck => Self::short_deck(),
+            _ => Self::default(),
+        };
+        Self { variant, ..parser }
+    }
+
+    /// A 36 card deck of sixes to aces.
+    pub fn short_deck() -> Self {
+        let ranks: Vec<Rank> = Rank::ranks()
+            .into_iter()
+            .filter(|r| *r >= Rank::Six)
+            .collect();
+        Self::new(&ranks)
+    }
+
+    pub fn ranks(&self) -> &[Rank] {
+        &self.ranks
+    }
+
+    pub fn hand_combinations(&self) -> usize {
+        self.hand_combinations
+    }
+
+    /// Like `calculate_range_percent`, over the hands of this deck.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::UnexpectedCardChar` - If the range can't be parsed.
+    /// * `Error::RankNotInDeck` - If the range uses a rank the deck lacks.
+    pub fn parse_percent(&self, s: &str) -> Result<f32> {
+        let combinations = parse_range(s)?;
+        let classes = combinations
+            .offsuit
+            .iter()
+            .chain(&combinations.suited)
+            .chain(&combinations.paired);
+        for class in classes {
+            let (rank1, rank2) = parse_class(class);
+            if let Some(r) = [rank1, rank2].into_iter().find(|r| !self.ranks.contains(r)) {
+                return Err(Error::RankNotInDeck(r));
+            }
+        }
+        let combos = match self.variant {
+            GameVariant::AceToFiveLowball => {
+                let unpaired: HashSet<&String> =
+                    combinations.offsuit.union(&combinations.suited).collect();
+                unpaired.len() * SPEC_UNPAIRED_COMBINATIONS
+                    + combinations.len_of_paired() * SPEC_PAIRED_COMBINATIONS
+            }
+            _ => combinations.combo_count(),
+        };
+        Ok(combos as f32 / self.hand_combinations as f32)
+    }
+}
+
+/// Parses a single hand class such as `AKs`, `KAo`, `AK` or `QQ` into the
+/// key stored in `Combinations`, with the higher rank first.
+fn parse_single_class(class: &str) -> Result<(String, HandType)> {
+    let class = class.trim();
+    if class.contains('+') {
+        return Err(Error::InvalidPlusModifier);
+    }
+    if !RANGE_REGEX.is_match(class) {
+        return Err(Error::UnexpectedCardChar);
+    }
+
+    let (high, low, hand_type) = parse_cards(class)?;
+    if high == low {
+        return Ok((format!("{}", high), HandType::Paired));
+    }
+    Ok((format!("{}{}", high, low), hand_type))
 }
 
-/// Calculates the percentage of hand combinations represented by the input string.
-///
-/// # Arguments
-///
-/// * `hand_range` - A string slice that holds the hand range.
-///
-/// # Returns
-///
-/// * `Result<f32, Error>` - The percentage of hand combinations.
-///
-/// # Errors
-///
-/// * `Error::UnexpectedCardChar` - If the input string contains unexpected characters.
 // pub fn calculate_range_percent(s: &str) -> Result<f32> {
 //     let s = TRIM_REGEX.replace_all(s, "$1").trim().to_string();
 //     let ranges: Vec<&str> = s.split(',').collect::<Vec<_>>();
@@ -106,7 +327,7 @@ impl Combinations {
 //     for range in ranges.into_iter() {
 //         let caps = RANGE_REGEX
 //             .captures(range)
-//             .ok_or_else(|| Error::UnexpectedCardChar)?;
+//             .ok_or(Error::UnexpectedCardChar)?;
 
 //         let matched_range = &caps[0];
 
@@ -120,32 +341,298 @@ impl Combinations {
 //     Ok(total_combinations as f32 / HAND_COMBINATIONS)
 // }
 
+/// Calculates the percentage of hand combinations represented by the input string.
+///
+/// # Arguments
+///
+/// * `hand_range` - A string slice that holds the hand range.
+///
+/// # Returns
+///
+/// * `Result<f32, Error>` - The percentage of hand combinations.
+///
+/// # Errors
+///
+/// * `Error::UnexpectedCardChar` - If the input string contains unexpected characters.
 pub fn calculate_range_percent(s: &str) -> Result<f32> {
+    RangeParser::default().parse_percent(s)
+}
+
+/// Like `calculate_range_percent`, over the 630 starting hands of a 36
+/// card six plus short deck.
+///
+/// # Errors
+///
+/// * `Error::UnexpectedCardChar` - If the input string contains unexpected characters.
+/// * `Error::RankNotInDeck` - If the range uses a two, three, four or five.
+pub fn calculate_range_percent_short(s: &str) -> Result<f32> {
+    RangeParser::short_deck().parse_percent(s)
+}
+
+/// Like `calculate_range_percent`, counting combos for ace-to-five
+/// lowball where suits don't matter, see `RangeParser::for_variant`.
+/// The ace still ranks high when expanding `+`, e.g. `22+` is every pair.
+///
+/// # Errors
+///
+/// * `Error::UnexpectedCardChar` - If the input string contains unexpected characters.
+pub fn calculate_range_percent_lowball(s: &str) -> Result<f32> {
+    RangeParser::for_variant(GameVariant::AceToFiveLowball).parse_percent(s)
+}
+
+/// Calculates the number of hand combinations represented by the input string.
+///
+/// # Arguments
+///
+/// * `hand_range` - A string slice that holds the hand range.
+///
+/// # Returns
+///
+/// * `Result<usize, Error>` - The number of hand combinations, out of 1326.
+///
+/// # Errors
+///
+/// * `Error::UnexpectedCardChar` - If the input string contains unexpected characters.
+pub fn calculate_range_combos(s: &str) -> Result<usize> {
+    Ok(parse_range(s)?.combo_count())
+}
+
+/// Lists all 169 distinct starting hand classes: the 13 pairs from `AA`
+/// down to `22`, then the 78 suited hands from `AKs` down to `32s`, then
+/// the 78 offsuit hands in the same order.
+pub fn all_starting_hands() -> Vec<String> {
+    all_starting_hands_with_combos()
+        .into_iter()
+        .map(|(class, _)| class)
+        .collect()
+}
+
+/// Same as `all_starting_hands`, paired with the number of combos of each
+/// class (6 for pairs, 4 for suited and 12 for offsuit hands).
+pub fn all_starting_hands_with_combos() -> Vec<(String, usize)> {
+    let mut ranks = Rank::ranks();
+    ranks.sort_by(|a, b| b.cmp(a));
+
+    let mut hands = Vec::with_capacity(169);
+    for rank in &ranks {
+        hands.push((format!("{}{}", rank, rank), SPEC_PAIRED_COMBINATIONS));
+    }
+    for (suffix, combos) in [
+        ('s', SPEC_SUITED_COMBINATIONS),
+        ('o', SPEC_OFF_SUIT_COMBINATIONS),
+    ] {
+        for (i, high) in ranks.iter().enumerate() {
+            for low in &ranks[i + 1..] {
+                hands.push((format!("{}{}{}", high, low, suffix), combos));
+            }
+        }
+    }
+    hands
+}
+
+/// Expands a hand range string into every concrete pair of hole cards it
+/// represents.
+///
+/// # Errors
+///
+/// * `Error::UnexpectedCardChar` - If the input string contains unexpected characters.
+pub fn range_combos(s: &str) -> Result<Vec<[Card; 2]>> {
+    let combinations = parse_range(s)?;
+    let mut combos = Vec::new();
+
+    for class in &combinations.offsuit {
+        let (rank1, rank2) = parse_class(class);
+        for s1 in &Suit::suits() {
+            for s2 in &Suit::suits() {
+                if s1 != s2 {
+                    combos.push([
+                        Card::new(s1.clone(), rank1.clone()),
+                        Card::new(s2.clone(), rank2.clone()),
+                    ]);
+                }
+            }
+        }
+    }
+
+    for class in &combinations.suited {
+        let (rank1, rank2) = parse_class(class);
+        for s in &Suit::suits() {
+            combos.push([
+                Card::new(s.clone(), rank1.clone()),
+                Card::new(s.clone(), rank2.clone()),
+            ]);
+        }
+    }
+
+    for class in &combinations.paired {
+        let (rank, _) = parse_class(class);
+        let suits = Suit::suits();
+        for (i, s1) in suits.iter().enumerate() {
+            for s2 in &suits[i + 1..] {
+                combos.push([
+                    Card::new(s1.clone(), rank.clone()),
+                    Card::new(s2.clone(), rank.clone()),
+                ]);
+            }
+        }
+    }
+
+    // The per class counts must agree with the combo constants.
+    debug_assert_eq!(combinations.combo_count(), combos.len());
+    combos.sort();
+    Ok(combos)
+}
+
+/// Expands a hand range string like `range_combos`, leaving out every combo
+/// that uses one of the dead cards.
+///
+/// # Errors
+///
+/// * `Error::UnexpectedCardChar` - If the input string contains unexpected characters.
+/// * `Error::DuplicateCardInRange` - If a dead card is listed twice.
+/// * `Error::EmptyRange` - If the dead cards block every combo.
+pub fn live_range_combos(s: &str, dead: &[Card]) -> Result<Vec<[Card; 2]>> {
+    let combos = unblocked_combos(s, dead)?;
+    if combos.is_empty() {
+        return Err(Error::EmptyRange);
+    }
+    Ok(combos)
+}
+
+/// Counts the combos of a hand range that don't use any of the dead
+/// cards, e.g. 3 of the 6 `AA` combos with an ace on the board.
+///
+/// # Errors
+///
+/// * `Error::UnexpectedCardChar` - If the input string contains unexpected characters.
+/// * `Error::DuplicateCardInRange` - If a dead card is listed twice.
+pub fn valid_combo_count(s: &str, dead: &[Card]) -> Result<usize> {
+    Ok(unblocked_combos(s, dead)?.len())
+}
+
+/// Counts the combos of a weighted range that don't use any of the dead
+/// cards, each one counting as its weight. A comma separated part may end
+/// in `:weight`, e.g. `AKs:0.5, QQ+` counts half of the live `AKs` combos
+/// and all of the live pairs. A combo listed again takes the later weight,
+/// and one removed with `!` is dropped whatever its weight.
+///
+/// # Errors
+///
+/// * `Error::UnexpectedCardChar` - If the input string contains unexpected characters.
+/// * `Error::InvalidRangeWeight` - If a weight isn't a number from 0 to 1.
+/// * `Error::DuplicateCardInRange` - If a dead card is listed twice.
+pub fn weighted_combo_count(range: &str, dead: &[Card]) -> Result<f32> {
+    let mut weights: BTreeMap<[Card; 2], f32> = BTreeMap::new();
+    for part in range.split(',') {
+        let (hands, weight) = match part.split_once(':') {
+            Some((hands, weight)) => {
+                let weight = weight
+                    .trim()
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|w| (0.0..=1.0).contains(w))
+                    .ok_or_else(|| Error::InvalidRangeWeight(part.trim().to_string()))?;
+                (hands, weight)
+            }
+            None => (part, 1.0),
+        };
+        // As in `parse_range`, anything after a `!` is removed from the
+        // combos weighted so far.
+        let mut hands = hands.split('!');
+        let included = hands.next().unwrap_or_default();
+        let excluded: Vec<&str> = hands.collect();
+        if !included.trim().is_empty() || excluded.is_empty() {
+            for combo in unblocked_combos(included, dead)? {
+                weights.insert(combo, weight);
+            }
+        }
+        for range in excluded {
+            for combo in range_combos(range)? {
+                weights.remove(&combo);
+            }
+        }
+    }
+    Ok(weights.values().sum())
+}
+
+fn unblocked_combos(s: &str, dead: &[Card]) -> Result<Vec<[Card; 2]>> {
+    for (i, c) in dead.iter().enumerate() {
+        if dead[i + 1..].contains(c) {
+            return Err(Error::DuplicateCardInRange(c.clone()));
+        }
+    }
+
+    Ok(range_combos(s)?
+        .into_iter()
+        .filter(|combo| !combo.iter().any(|c| dead.contains(c)))
+        .collect())
+}
+
+pub(crate) fn parse_range(s: &str) -> Result<Combinations> {
     let s = TRIM_REGEX.replace_all(s, "$1").trim().to_string();
     let ranges: Vec<&str> = s.split(',').collect::<Vec<_>>();
     let mut combinations = Combinations::new();
     for range in ranges.into_iter() {
-        let caps = RANGE_REGEX
-            .captures(range)
-            .ok_or_else(|| Error::UnexpectedCardChar)?;
+        // Anything after a `!` is removed from what has been parsed so far.
+        let mut parts = range.split('!');
+        let included = parts.next().unwrap_or_default().trim();
+        let excluded: Vec<&str> = parts.map(str::trim).collect();
 
-        let matched_range = &caps[0];
+        if !included.is_empty() || excluded.is_empty() {
+            generate_combinations(included, &mut combinations)?;
+        }
+        for range in excluded {
+            let mut removed = Combinations::new();
+            generate_combinations(range, &mut removed)?;
+            combinations
+                .offsuit
+                .retain(|c| !removed.offsuit.contains(c));
+            combinations.suited.retain(|c| !removed.suited.contains(c));
+            combinations.paired.retain(|c| !removed.paired.contains(c));
+        }
+    }
+    Ok(combinations)
+}
 
-        if range.contains('+') {
-            generate_plus_combinations(matched_range, &mut combinations)
-        } else {
-            generate_single_combinations(matched_range, &mut combinations)
-        };
+fn generate_combinations(range: &str, combinations: &mut Combinations) -> Result<()> {
+    if let Some(percent) = range.strip_suffix('%') {
+        let percent: f32 = percent
+            .parse()
+            .ok()
+            .filter(|p| (0.0..=100.0).contains(p))
+            .ok_or_else(|| Error::InvalidRangePercent(range.to_string()))?;
+        for class in top_percent_classes(percent) {
+            generate_single_combinations(&class, combinations)?;
+        }
+        return Ok(());
+    }
+
+    let caps = RANGE_REGEX
+        .captures(range)
+        .ok_or(Error::UnexpectedCardChar)?;
+
+    let matched_range = &caps[0];
+
+    if range.contains('+') {
+        generate_plus_combinations(matched_range, combinations)
+    } else {
+        generate_single_combinations(matched_range, combinations)
     }
+}
 
-    let total_count = combinations.len_of_offsuit() * SPEC_OFF_SUIT_COMBINATIONS
-        + combinations.len_of_suited() * SPEC_SUITED_COMBINATIONS
-        + combinations.len_of_paired() * SPEC_PAIRED_COMBINATIONS;
-    Ok(total_count as f32 / HAND_COMBINATIONS)
+/// Parses a stored hand class such as `AK` or `A` (a pair) back into ranks.
+fn parse_class(s: &str) -> (Rank, Rank) {
+    let mut chars = s.chars();
+    let rank1 = Rank::from_char(chars.next().unwrap()).unwrap();
+    let rank2 = chars
+        .next()
+        .map(|c| Rank::from_char(c).unwrap())
+        .unwrap_or_else(|| rank1.clone());
+    (rank1, rank2)
 }
 
-fn generate_plus_combinations(s: &str, combinations: &mut Combinations) {
-    let (rank1, rank2, hand_type) = parse_cards(s);
+fn generate_plus_combinations(s: &str, combinations: &mut Combinations) -> Result<()> {
+    let (rank1, rank2, hand_type) = parse_cards(s)?;
 
     match hand_type {
         HandType::Offsuit => {
@@ -154,7 +641,7 @@ fn generate_plus_combinations(s: &str, combinations: &mut Combinations) {
                 let v = rank2.as_int() + i;
                 combinations
                     .offsuit
-                    .insert(format!("{}{}", rank1, Rank::from_int(v).unwrap()));
+                    .insert(format!("{}{}", rank1, Rank::try_from_int(v)?));
             }
         }
         HandType::Suited => {
@@ -163,7 +650,7 @@ fn generate_plus_combinations(s: &str, combinations: &mut Combinations) {
                 let v = rank2.as_int() + i;
                 combinations
                     .suited
-                    .insert(format!("{}{}", rank1, Rank::from_int(v).unwrap()));
+                    .insert(format!("{}{}", rank1, Rank::try_from_int(v)?));
             }
         }
         HandType::Paired => {
@@ -172,7 +659,7 @@ fn generate_plus_combinations(s: &str, combinations: &mut Combinations) {
                 let v = rank1.as_int() + i;
                 combinations
                     .paired
-                    .insert(format!("{}", Rank::from_int(v).unwrap()));
+                    .insert(format!("{}", Rank::try_from_int(v)?));
             }
         }
         HandType::UnPaired => {
@@ -181,17 +668,18 @@ fn generate_plus_combinations(s: &str, combinations: &mut Combinations) {
                 let v = rank2.as_int() + i;
                 combinations
                     .offsuit
-                    .insert(format!("{}{}", rank1, Rank::from_int(v).unwrap()));
+                    .insert(format!("{}{}", rank1, Rank::try_from_int(v)?));
                 combinations
                     .suited
-                    .insert(format!("{}{}", rank1, Rank::from_int(v).unwrap()));
+                    .insert(format!("{}{}", rank1, Rank::try_from_int(v)?));
             }
         }
     }
+    Ok(())
 }
 
-fn generate_single_combinations(s: &str, combinations: &mut Combinations) {
-    let (rank1, rank2, hand_type) = parse_cards(s);
+fn generate_single_combinations(s: &str, combinations: &mut Combinations) -> Result<()> {
+    let (rank1, rank2, hand_type) = parse_cards(s)?;
     match hand_type {
         HandType::Offsuit => {
             combinations.offsuit.insert(format!("{}{}", rank1, rank2));
@@ -207,6 +695,7 @@ fn generate_single_combinations(s: &str, combinations: &mut Combinations) {
             combinations.suited.insert(format!("{}{}", rank1, rank2));
         }
     }
+    Ok(())
 }
 
 // fn calculate_plus_range(s: &str) -> usize {
@@ -242,11 +731,23 @@ fn generate_single_combinations(s: &str, combinations: &mut Combinations) {
 //     }
 // }
 
-fn parse_cards(s: &str) -> (Rank, Rank, HandType) {
+/// Reads the two ranks and suffix of a class, with the higher rank first
+/// whichever way round it was written, so `KAs` and `AKs` give the same
+/// `Combinations` key.
+fn parse_cards(s: &str) -> Result<(Rank, Rank, HandType)> {
     let mut chars = s.chars();
-    let rank1 = Rank::from_char(chars.next().unwrap()).unwrap();
-    let rank2 = Rank::from_char(chars.next().unwrap()).unwrap();
+    let mut next_rank = || {
+        let c = chars.next().ok_or(Error::UnexpectedCardChar)?;
+        Rank::from_char(c).ok_or(Error::UnexpectedRankChar)
+    };
+    let (rank1, rank2) = match (next_rank()?, next_rank()?) {
+        (a, b) if a < b => (b, a),
+        ranks => ranks,
+    };
     let hand_type = match chars.next() {
+        // A pair can't be suited, and saying it's offsuit would count its
+        // combos twice.
+        Some('o' | 's') if rank1 == rank2 => return Err(Error::InvalidSuitedPairs),
         Some('o') => HandType::Offsuit,
         Some('s') => HandType::Suited,
         _ => {
@@ -258,7 +759,7 @@ fn parse_cards(s: &str) -> (Rank, Rank, HandType) {
         }
     };
 
-    (rank1, rank2, hand_type)
+    Ok((rank1, rank2, hand_type))
 }
 
 #[cfg(test)]
@@ -322,6 +823,345 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_range_parser() {
+        let parser = RangeParser::default();
+        assert_eq!(1326, parser.hand_combinations());
+        assert_eq!(
+            calculate_range_percent("88+, AJo+").unwrap(),
+            parser.parse_percent("88+, AJo+").unwrap()
+        );
+
+        let short = RangeParser::short_deck();
+        assert_eq!(9, short.ranks().len());
+        assert_eq!(630, short.hand_combinations());
+        assert!(matches!(
+            short.parse_percent("22"),
+            Err(Error::RankNotInDeck(Rank::Two))
+        ));
+        assert!(short.parse_percent("A2s+").is_err());
+        assert_eq!(6.0 / 630.0, short.parse_percent("AA").unwrap());
+        assert_eq!(54.0 / 630.0, short.parse_percent("66+").unwrap());
+        assert_eq!(60.0 / 630.0, short.parse_percent("A9o+").unwrap());
+    }
+
+    #[test]
+    fn test_short_deck_range_percent() {
+        // 4 pairs of 6 combos, plus 4 suited and 12 offsuit AK combos.
+        assert_eq!(
+            (4.0 * 6.0 + 16.0) / 630.0,
+            calculate_range_percent_short("JJ+, AK").unwrap()
+        );
+        let every_hand = "66+, A6+, K6+, Q6+, J6+, T6+, 96+, 86+, 76";
+        assert_eq!(1.0, calculate_range_percent_short(every_hand).unwrap());
+        assert!(matches!(
+            calculate_range_percent_short("A5s"),
+            Err(Error::RankNotInDeck(Rank::Five))
+        ));
+        assert!(calculate_range_percent_short("22+").is_err());
+    }
+
+    #[test]
+    fn test_lowball_range_percent() {
+        let percent = |s: &str| calculate_range_percent_lowball(s).unwrap();
+        assert_eq!(16.0 / 1326.0, percent("A2"));
+        assert_eq!(16.0 / 1326.0, percent("A2s"));
+        assert_eq!(16.0 / 1326.0, percent("A2s, A2o"));
+        assert_eq!(22.0 / 1326.0, percent("A2, 33"));
+        // `+` still counts up towards the ace.
+        assert_eq!(78.0 / 1326.0, percent("22+"));
+        assert_eq!(11.0 * 16.0 / 1326.0, percent("K2+"));
+        assert_eq!(4.0 / 1326.0, calculate_range_percent("A2s").unwrap());
+
+        let short = RangeParser::for_variant(GameVariant::ShortDeck);
+        assert_eq!(RangeParser::short_deck().ranks(), short.ranks());
+        assert_eq!(630, short.hand_combinations());
+        let deuce_seven = RangeParser::for_variant(GameVariant::Deuce7Lowball);
+        assert_eq!(4.0 / 1326.0, deuce_seven.parse_percent("72s").unwrap());
+        assert!(calculate_range_percent_lowball("A1").is_err());
+    }
+
+    #[test]
+    fn test_calculate_range_combos() {
+        assert_eq!(6, calculate_range_combos("AA").unwrap());
+        assert_eq!(78, calculate_range_combos("22+").unwrap());
+        assert_eq!(16, calculate_range_combos("AK").unwrap());
+        assert_eq!(22, calculate_range_combos("AA, AKs, AKo").unwrap());
+        assert!(calculate_range_combos("AKx").is_err());
+    }
+
+    #[test]
+    fn test_range_set_toggle() {
+        let mut range = RangeSet::parse("QQ+").unwrap();
+        assert_eq!(18, range.combo_count());
+
+        assert!(range.insert("AKs").unwrap());
+        assert!(!range.insert("AKs").unwrap());
+        assert!(range.contains("AKs").unwrap());
+        assert!(!range.contains("AK").unwrap());
+        assert_eq!(22, range.combo_count());
+
+        assert!(range.remove("AKs").unwrap());
+        assert!(!range.contains("AKs").unwrap());
+        assert_eq!(18, range.combo_count());
+    }
+
+    #[test]
+    fn test_range_set_low_first() {
+        let mut range = RangeSet::parse("KAs, 9T, 8A+").unwrap();
+        assert!(range.contains("KAs").unwrap());
+        assert!(range.contains("AKs").unwrap());
+        assert!(range.contains("T9").unwrap());
+        assert!(range.contains("A8").unwrap());
+        assert_eq!(16 + 6 * 16, range.combo_count());
+
+        assert!(!range.insert("AKs").unwrap());
+        assert_eq!(16 + 6 * 16, range.combo_count());
+        assert_eq!(range, RangeSet::parse("T9, A8+").unwrap());
+
+        assert!(range.remove("KAs").unwrap());
+        assert!(!range.contains("AKs").unwrap());
+        assert!(range.contains("AKo").unwrap());
+        assert_eq!(16 + 5 * 16 + 12, range.combo_count());
+    }
+
+    #[test]
+    fn test_range_set_classes() {
+        let mut range = RangeSet::new();
+        assert!(range.is_empty());
+        range.insert("KA").unwrap();
+        assert!(range.contains("AKo").unwrap());
+        assert!(range.contains("AKs").unwrap());
+        assert_eq!(16, range.combo_count());
+        range.insert("tt").unwrap();
+        assert!(range.contains("TT").unwrap());
+
+        assert!(range.insert("AKo+").is_err());
+        assert!(range.insert("AAs").is_err());
+        assert!(range.insert("AKx").is_err());
+    }
+
+    #[test]
+    fn test_range_set_to_string() {
+        let mut range = RangeSet::new();
+        for class in ["T9o", "KK", "AKs", "AA", "AKo", "QJs"] {
+            range.insert(class).unwrap();
+        }
+        assert_eq!("AA, KK, AKs, QJs, AKo, T9o", range.to_string());
+        assert_eq!(range, RangeSet::parse(&range.to_string()).unwrap());
+    }
+
+    #[test]
+    fn test_all_starting_hands() {
+        let hands = all_starting_hands_with_combos();
+        assert_eq!(169, hands.len());
+        assert_eq!(1326, hands.iter().map(|(_, combos)| combos).sum::<usize>());
+
+        let classes = all_starting_hands();
+        assert_eq!(169, classes.iter().collect::<HashSet<_>>().len());
+        assert_eq!("AA", classes[0]);
+        assert_eq!("22", classes[12]);
+        assert_eq!("AKs", classes[13]);
+        assert_eq!("32o", classes[168]);
+
+        for (class, combos) in hands {
+            assert_eq!(combos, calculate_range_combos(&class).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_exclusions() {
+        assert_eq!(72, calculate_range_combos("22+ !55").unwrap());
+        assert_eq!(72, calculate_range_combos("22+!55").unwrap());
+        assert_eq!(66, calculate_range_combos("22+ !55 !66").unwrap());
+        assert_eq!(52, calculate_range_combos("22+ !TT+, AKs").unwrap());
+        assert_eq!(12, calculate_range_combos("AK !AKs").unwrap());
+
+        let combos = range_combos("22+ !55").unwrap();
+        assert!(!combos
+            .iter()
+            .any(|[c1, c2]| c1.rank() == &Rank::Five && c2.rank() == &Rank::Five));
+
+        assert!(calculate_range_combos("22+ !5x").is_err());
+        assert!(calculate_range_combos("22+ !").is_err());
+    }
+
+    #[test]
+    fn test_combo_constants_match_enumeration() {
+        let cases = [
+            ("AKo", HandType::Offsuit, SPEC_OFF_SUIT_COMBINATIONS),
+            ("AKs", HandType::Suited, SPEC_SUITED_COMBINATIONS),
+            ("AA", HandType::Paired, SPEC_PAIRED_COMBINATIONS),
+            (
+                "AK",
+                HandType::UnPaired,
+                SPEC_OFF_SUIT_COMBINATIONS + SPEC_SUITED_COMBINATIONS,
+            ),
+        ];
+        for (class, hand_type, expected) in cases {
+            assert_eq!(hand_type, parse_cards(class).unwrap().2);
+            assert_eq!(expected, range_combos(class).unwrap().len(), "{}", class);
+            assert_eq!(
+                expected,
+                calculate_range_combos(class).unwrap(),
+                "{}",
+                class
+            );
+        }
+
+        for (class, combos) in all_starting_hands_with_combos() {
+            assert_eq!(combos, range_combos(&class).unwrap().len(), "{}", class);
+        }
+    }
+
+    #[test]
+    fn test_valid_combo_count() {
+        let ace = Card::new(Suit::Spade, Rank::Ace);
+        let king = Card::new(Suit::Heart, Rank::King);
+        assert_eq!(6, valid_combo_count("AA", &[]).unwrap());
+        assert_eq!(
+            3,
+            valid_combo_count("AA", std::slice::from_ref(&ace)).unwrap()
+        );
+        assert_eq!(
+            2,
+            valid_combo_count("AKs", &[ace.clone(), king.clone()]).unwrap()
+        );
+        assert_eq!(
+            8,
+            valid_combo_count("AKs, QQ", &[ace.clone(), king]).unwrap()
+        );
+
+        let aces: Vec<Card> = Card::iter_rank(Rank::Ace).take(3).collect();
+        assert_eq!(0, valid_combo_count("AA", &aces).unwrap());
+        assert!(valid_combo_count("AA", &[ace.clone(), ace]).is_err());
+    }
+
+    #[test]
+    fn test_weighted_combo_count() {
+        let ace = Card::new(Suit::Spade, Rank::Ace);
+        let blocked = std::slice::from_ref(&ace);
+        assert_eq!(2.0, weighted_combo_count("AKs:0.5", &[]).unwrap());
+        assert_eq!(1.5, weighted_combo_count("AKs:0.5", blocked).unwrap());
+        assert_eq!(
+            1.5 + 6.0,
+            weighted_combo_count("AA:0.5, KK", blocked).unwrap()
+        );
+        assert_eq!(1.5, weighted_combo_count("AA, AA:0.25", &[]).unwrap());
+        assert_eq!(
+            valid_combo_count("QQ+", blocked).unwrap() as f32,
+            weighted_combo_count("QQ+", blocked).unwrap()
+        );
+
+        for range in ["AA:2", "AA:x", "AA:-0.5"] {
+            assert!(matches!(
+                weighted_combo_count(range, &[]),
+                Err(Error::InvalidRangeWeight(_))
+            ));
+        }
+        assert!(weighted_combo_count("AX:0.5", &[]).is_err());
+
+        for range in ["QQ+, !KK", "QQ+!KK", "AKs, QQ+, !KK, AKo"] {
+            assert_eq!(
+                valid_combo_count(range, blocked).unwrap() as f32,
+                weighted_combo_count(range, blocked).unwrap()
+            );
+        }
+        assert_eq!(12.0, weighted_combo_count("QQ+, !KK", &[]).unwrap());
+        assert_eq!(
+            4.0 + 6.0,
+            weighted_combo_count("AKs, QQ+:0.5, !KK", &[]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_live_range_combos() {
+        let ace = Card::new(Suit::Spade, Rank::Ace);
+        let king = Card::new(Suit::Heart, Rank::King);
+        assert_eq!(
+            3,
+            live_range_combos("AA", std::slice::from_ref(&ace))
+                .unwrap()
+                .len()
+        );
+        assert_eq!(
+            7,
+            live_range_combos("AKo", &[ace.clone(), king.clone()])
+                .unwrap()
+                .len()
+        );
+
+        assert!(matches!(
+            live_range_combos("AA", &[ace.clone(), king, ace]),
+            Err(Error::DuplicateCardInRange(_))
+        ));
+
+        let aces: Vec<Card> = Suit::suits()
+            .into_iter()
+            .take(3)
+            .map(|s| Card::new(s, Rank::Ace))
+            .collect();
+        assert!(matches!(
+            live_range_combos("AA", &aces),
+            Err(Error::EmptyRange)
+        ));
+    }
+
+    #[test]
+    fn test_parse_cards_malformed() {
+        assert!(matches!(parse_cards("A"), Err(Error::UnexpectedCardChar)));
+        assert!(matches!(parse_cards(""), Err(Error::UnexpectedCardChar)));
+        assert!(matches!(parse_cards("AX"), Err(Error::UnexpectedRankChar)));
+        assert!(matches!(parse_cards("1Ks"), Err(Error::UnexpectedRankChar)));
+        assert_eq!(
+            (Rank::Ace, Rank::King, HandType::Suited),
+            parse_cards("AKs").unwrap()
+        );
+        assert_eq!(
+            (Rank::Ace, Rank::King, HandType::Offsuit),
+            parse_cards("KAo").unwrap()
+        );
+
+        let mut combinations = Combinations::new();
+        assert!(generate_single_combinations("Z9", &mut combinations).is_err());
+        assert!(generate_plus_combinations("9", &mut combinations).is_err());
+        assert!(combinations.contains_class("Q").is_err());
+    }
+
+    #[test]
+    fn test_top_percent() {
+        assert_eq!(1.0, calculate_range_percent("100%").unwrap());
+        assert_eq!(0, calculate_range_combos("0%").unwrap());
+
+        let premium = parse_range("2%").unwrap();
+        for class in ["AA", "KK", "QQ", "JJ"] {
+            assert!(premium.contains_class(class).unwrap());
+        }
+        assert_eq!(24, premium.combo_count());
+
+        let top = calculate_range_combos("20%").unwrap();
+        assert_eq!(top, calculate_range_combos("20%, AA").unwrap());
+        assert_eq!(top + 12, calculate_range_combos("20%, 72o").unwrap());
+        assert_eq!(18, calculate_range_combos("2% !JJ").unwrap());
+
+        for bad in ["101%", "x%", "%"] {
+            assert!(matches!(
+                calculate_range_combos(bad),
+                Err(Error::InvalidRangePercent(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_plus_past_ace() {
+        // Walking up from an ace used to run past it. Written low first, the
+        // class now walks up from the deuce like `A2s+` does.
+        assert_eq!(
+            calculate_range_percent("A2s+").unwrap(),
+            calculate_range_percent("2As+").unwrap()
+        );
+    }
+
     #[test]
     fn test_calculate_range_percent_invalid_input() {
         let invalid_inputs = [
@@ -333,4 +1173,27 @@ mod tests {
             assert!(result.is_err(), "Expected error for input: {}", input);
         }
     }
+
+    #[test]
+    fn test_range_combos() {
+        assert_eq!(6, range_combos("AA").unwrap().len());
+        assert_eq!(4, range_combos("AKs").unwrap().len());
+        assert_eq!(12, range_combos("AKo").unwrap().len());
+        assert_eq!(16, range_combos("AK").unwrap().len());
+        assert_eq!(78, range_combos("22+").unwrap().len());
+
+        let combos: HashSet<[Card; 2]> = range_combos("QQ+, AK").unwrap().into_iter().collect();
+        assert_eq!(34, combos.len());
+        assert!(range_combos("AKx").is_err());
+
+        let aces: HashSet<[Card; 2]> = range_combos("AA").unwrap().into_iter().collect();
+        assert_eq!(6, aces.len());
+        assert!(aces.iter().all(|[a, b]| a != b));
+        for range in ["AAs", "AAo", "QQs+", "TTo+"] {
+            assert!(matches!(
+                range_combos(range),
+                Err(Error::InvalidSuitedPairs)
+            ));
+        }
+    }
 }
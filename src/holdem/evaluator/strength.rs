@@ -0,0 +1,120 @@
+//! # Preflop Hand Strength
+//!
+//! Orders the 169 starting hand classes by the Chen formula, a quick
+//! estimate of preflop strength, so ranges like "the top 20%" can be
+//! expanded into concrete hand classes.
+
+use once_cell::sync::Lazy;
+
+use crate::poker::card::Rank;
+
+use super::range::all_starting_hands_with_combos;
+
+/// Total number of two card starting hands.
+const HAND_COMBINATIONS: usize = 1326;
+
+/// All 169 classes with their combo counts, strongest first. Classes with
+/// the same score keep the order of `all_starting_hands`.
+static STRENGTH_ORDER: Lazy<Vec<(String, usize)>> = Lazy::new(|| {
+    let mut hands = all_starting_hands_with_combos();
+    hands.sort_by_key(|(class, _)| std::cmp::Reverse(chen_score(class)));
+    hands
+});
+
+/// Chen formula score of a class such as `AKs`, `T9o` or `77`, doubled so
+/// that it stays an integer. Ranges from -2 for hands like `72o` up to 40
+/// for `AA`.
+pub fn chen_score(class: &str) -> i32 {
+    let mut chars = class.chars();
+    let (Some(high), Some(low)) = (
+        chars.next().and_then(Rank::from_char),
+        chars.next().and_then(Rank::from_char),
+    ) else {
+        return 0;
+    };
+    let (high, low) = if high >= low {
+        (high, low)
+    } else {
+        (low, high)
+    };
+    let card_points = |rank: &Rank| match rank {
+        Rank::Ace => 20,
+        Rank::King => 16,
+        Rank::Queen => 14,
+        Rank::Jack => 12,
+        other => other.as_int() as i32,
+    };
+
+    if high == low {
+        return (card_points(&high) * 2).max(10);
+    }
+    let mut score = card_points(&high);
+    if chars.next() == Some('s') {
+        score += 4;
+    }
+    let gap = high.gap(&low) - 1;
+    score -= match gap {
+        0 => 0,
+        1 => 2,
+        2 => 4,
+        3 => 8,
+        _ => 10,
+    };
+    if gap <= 1 && high < Rank::Queen {
+        score += 2;
+    }
+    // The formula rounds half points up, also for negative scores.
+    score + score.rem_euclid(2)
+}
+
+/// The strongest classes that together make up at most `percent` of all
+/// starting hands.
+pub fn top_percent_classes(percent: f32) -> Vec<String> {
+    let limit = (percent / 100.0 * HAND_COMBINATIONS as f32).round() as usize;
+    let mut total = 0;
+    STRENGTH_ORDER
+        .iter()
+        .take_while(|(_, combos)| {
+            total += combos;
+            total <= limit
+        })
+        .map(|(class, _)| class.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chen_score() {
+        assert_eq!(40, chen_score("AA"));
+        assert_eq!(24, chen_score("AKs"));
+        assert_eq!(10, chen_score("22"));
+        assert_eq!(12, chen_score("T9o"));
+        assert_eq!(16, chen_score("T9s"));
+        // 7-5 suited: 3.5 + 2 - 1 + 1 = 5.5, rounded up to 6.
+        assert_eq!(12, chen_score("75s"));
+        assert_eq!(0, chen_score("x"));
+        assert_eq!(6, chen_score("32o"));
+        // 7-2 offsuit: 3.5 - 5 = -1.5, rounded up to -1.
+        assert_eq!(-2, chen_score("72o"));
+        assert_eq!(-2, chen_score("82o"));
+        assert_eq!(
+            Some(-2),
+            all_starting_hands_with_combos()
+                .iter()
+                .map(|(c, _)| chen_score(c))
+                .min()
+        );
+    }
+
+    #[test]
+    fn test_top_percent_classes() {
+        assert_eq!(169, top_percent_classes(100.0).len());
+        assert_eq!(vec!["AA", "KK", "QQ", "JJ"], top_percent_classes(2.0));
+        assert!(top_percent_classes(0.0).is_empty());
+        let weakest = &STRENGTH_ORDER[STRENGTH_ORDER.len() - 1].0;
+        assert_eq!(-2, chen_score(weakest));
+    }
+}
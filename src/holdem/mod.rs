@@ -1 +1,4 @@
+pub mod board;
+pub mod chart;
+pub mod equity;
 pub mod evaluator;
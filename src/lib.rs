@@ -1,3 +1,7 @@
+#[macro_use]
+mod macros;
+
 pub mod error;
 pub mod holdem;
 pub mod poker;
+pub mod stud;
@@ -0,0 +1,54 @@
+/// Parses a card such as `card!("As")`, panicking on invalid input. Meant
+/// for tests and scripts, use `Card::try_from_str` to handle errors.
+#[macro_export]
+macro_rules! card {
+    ($s:expr) => {{
+        let s: &str = $s;
+        $crate::poker::card::Card::try_from_str(s)
+            .unwrap_or_else(|e| panic!("invalid card {:?}: {}", s, e))
+    }};
+}
+
+/// Builds a `Hand` from card strings such as `hand!["As", "Ks", "Qs"]`,
+/// panicking on invalid input like `card!`.
+#[macro_export]
+macro_rules! hand {
+    ($($s:expr),* $(,)?) => {
+        $crate::poker::hand::Hand::new_with_cards(vec![$($crate::card!($s)),*])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::poker::{
+        card::{Card, Rank, Suit},
+        hand::Hand,
+    };
+
+    #[test]
+    fn test_card() {
+        assert_eq!(Card::new(Suit::Spade, Rank::Ace), card!("As"));
+        assert_eq!(Card::new(Suit::Heart, Rank::Ten), card!("♥T"));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid card \"Ax\"")]
+    fn test_card_invalid() {
+        card!("Ax");
+    }
+
+    #[test]
+    fn test_hand() {
+        let hand = hand!["As", "Ks", "Qs"];
+        let expected = Hand::new_from_strs(&["As", "Ks", "Qs"]).unwrap();
+        assert_eq!(expected.cards(), hand.cards());
+        assert_eq!(0, hand![].len());
+        assert_eq!(2, hand!["2c", "3d",].len());
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid card \"Zz\"")]
+    fn test_hand_invalid() {
+        hand!["As", "Zz"];
+    }
+}
@@ -0,0 +1,35 @@
+use std::io::BufRead;
+
+use crate::error::{Error, Result};
+
+use super::{
+    hand::Hand,
+    rank::{HandRanker, Rank},
+};
+
+/// Ranks one hand per line of `r`, each line holding space separated cards
+/// such as `SA HK D9 C7 S2`. There is one result per line, so a bad line
+/// doesn't stop the ones after it.
+pub fn evaluate_reader<R: BufRead>(r: R) -> impl Iterator<Item = Result<Rank>> {
+    r.lines().map(|line| {
+        let line = line.map_err(|e| Error::ReadFailed(e.to_string()))?;
+        let cards: Vec<&str> = line.split_whitespace().collect();
+        Ok(Hand::new_from_strs(&cards)?.rank())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluate_reader() {
+        let input = "SA HA D9 C9 S2\nst sj sq sk sa\nSA HX\nh2 h3 h4 h5 h6 d2 c2\n";
+        let results: Vec<Result<Rank>> = evaluate_reader(input.as_bytes()).collect();
+        assert_eq!(4, results.len());
+        assert!(results[0].as_ref().unwrap().is_two_pair());
+        assert_eq!(&Rank::StraightFlush(9), results[1].as_ref().unwrap());
+        assert!(matches!(results[2], Err(Error::UnexpectedCardChar)));
+        assert!(results[3].as_ref().unwrap().is_straight_flush());
+    }
+}
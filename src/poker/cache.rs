@@ -0,0 +1,119 @@
+use std::collections::{HashMap, VecDeque};
+
+use super::{
+    card::Card,
+    rank::{HandRanker, Rank},
+};
+
+/// Memoizes `HandRanker::rank` results keyed on the sorted set of cards.
+///
+/// The cache holds at most `capacity` entries; once full, the oldest entry
+/// is evicted to make room for a new one.
+#[derive(Debug, Clone)]
+pub struct CachedRanker {
+    cache: HashMap<Vec<Card>, Rank>,
+    order: VecDeque<Vec<Card>>,
+    capacity: usize,
+}
+
+impl CachedRanker {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            cache: HashMap::with_capacity(capacity),
+            order: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Ranks the cards, reusing a previous result for the same card set.
+    pub fn rank<T: HandRanker + ?Sized>(&mut self, hand: &T) -> Rank {
+        let mut key = hand.cards().to_vec();
+        key.sort();
+
+        if let Some(rank) = self.cache.get(&key) {
+            return rank.clone();
+        }
+
+        let rank = hand.rank();
+        if self.capacity == 0 {
+            return rank;
+        }
+        if self.cache.len() >= self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.cache.remove(&oldest);
+            }
+        }
+        self.order.push_back(key.clone());
+        self.cache.insert(key, rank.clone());
+        rank
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.order.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poker::hand::Hand;
+
+    #[test]
+    fn test_matches_direct_rank() {
+        let hands = [
+            ["da", "dk", "dq", "dj", "dt", "d9", "d8"],
+            ["s2", "h2", "d2", "c2", "dk", "h9", "s4"],
+            ["h2", "d2", "d8", "s8", "dk", "s6", "ht"],
+            ["c6", "c7", "h8", "h9", "st", "ck", "s6"],
+        ];
+        let mut cached = CachedRanker::new(16);
+        for _ in 0..2 {
+            for h in &hands {
+                let hand = Hand::new_from_strs(h).unwrap();
+                assert_eq!(hand.rank(), cached.rank(&hand));
+            }
+        }
+        assert_eq!(hands.len(), cached.len());
+    }
+
+    #[test]
+    fn test_same_cards_any_order() {
+        let mut cached = CachedRanker::new(4);
+        let a = Hand::new_from_strs(&["da", "ca", "d9", "c9", "s9"]).unwrap();
+        let b = Hand::new_from_strs(&["s9", "c9", "d9", "ca", "da"]).unwrap();
+        assert_eq!(cached.rank(&a), cached.rank(&b));
+        assert_eq!(1, cached.len());
+    }
+
+    #[test]
+    fn test_bounded() {
+        let mut cached = CachedRanker::new(2);
+        let hands = [
+            ["da", "ca", "d9", "c9", "s9"],
+            ["da", "h8", "c9", "ct", "c5"],
+            ["c2", "s3", "h4", "s5", "d6"],
+        ];
+        for h in &hands {
+            cached.rank(&Hand::new_from_strs(h).unwrap());
+            assert!(cached.len() <= 2);
+        }
+        assert_eq!(2, cached.len());
+
+        let mut disabled = CachedRanker::new(0);
+        disabled.rank(&Hand::new_from_strs(&hands[0]).unwrap());
+        assert!(disabled.is_empty());
+    }
+}
@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{cmp::Ordering, fmt};
 
 use serde::de;
 
@@ -16,17 +16,63 @@ pub enum Suit {
 /// All of the Suits
 const SUITS: [Suit; 4] = [Suit::Spade, Suit::Heart, Suit::Diamond, Suit::Club];
 
+/// An order to list suits in, see `Suit::iter_in_order`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum SuitOrder {
+    /// The derived `Ord`: clubs, diamonds, hearts, spades.
+    #[default]
+    Derived,
+    /// Highest bridge suit first: spades, hearts, diamonds, clubs.
+    Bridge,
+}
+
 impl Suit {
     pub const fn suits() -> [Self; 4] {
         SUITS
     }
 
-    pub fn from_char(c: char) -> Option<Self> {
+    /// All four suits in the given order.
+    pub fn iter_in_order(order: SuitOrder) -> impl Iterator<Item = Suit> {
+        let mut suits = SUITS;
+        match order {
+            SuitOrder::Derived => suits.sort(),
+            SuitOrder::Bridge => suits.sort_by(|a, b| b.cmp(a)),
+        }
+        suits.into_iter()
+    }
+
+    pub const fn from_char(c: char) -> Option<Self> {
         match c.to_ascii_uppercase() {
             'S' => Some(Suit::Spade),
             'H' => Some(Suit::Heart),
             'D' => Some(Suit::Diamond),
             'C' => Some(Suit::Club),
+            '♠' | '♤' => Some(Suit::Spade),
+            '♥' | '♡' => Some(Suit::Heart),
+            '♦' | '♢' => Some(Suit::Diamond),
+            '♣' | '♧' => Some(Suit::Club),
+            _ => None,
+        }
+    }
+
+    /// The suit's place in the card ordering, `0` for clubs up to `3` for
+    /// spades.
+    pub const fn index(&self) -> u8 {
+        match self {
+            Suit::Club => 0,
+            Suit::Diamond => 1,
+            Suit::Heart => 2,
+            Suit::Spade => 3,
+        }
+    }
+
+    /// Reverses `index`, returning `None` for indices of 4 and above.
+    pub const fn from_index(index: u8) -> Option<Self> {
+        match index {
+            0 => Some(Suit::Club),
+            1 => Some(Suit::Diamond),
+            2 => Some(Suit::Heart),
+            3 => Some(Suit::Spade),
             _ => None,
         }
     }
@@ -40,6 +86,16 @@ impl Suit {
         }
     }
 
+    /// The English name, e.g. "Spade".
+    pub fn name(&self) -> &'static str {
+        match self {
+            Suit::Spade => "Spade",
+            Suit::Heart => "Heart",
+            Suit::Diamond => "Diamond",
+            Suit::Club => "Club",
+        }
+    }
+
     fn as_icon_char(&self) -> char {
         match self {
             Suit::Spade => '♠',
@@ -90,7 +146,7 @@ impl Rank {
         RANKS
     }
 
-    pub fn from_char(c: char) -> Option<Self> {
+    pub const fn from_char(c: char) -> Option<Self> {
         match c.to_ascii_uppercase() {
             'A' => Some(Rank::Ace),
             '2' => Some(Rank::Two),
@@ -127,7 +183,26 @@ impl Rank {
         }
     }
 
-    pub fn from_int(i: i8) -> Option<Self> {
+    /// The English name, e.g. "Ace".
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rank::Ace => "Ace",
+            Rank::Two => "Two",
+            Rank::Three => "Three",
+            Rank::Four => "Four",
+            Rank::Five => "Five",
+            Rank::Six => "Six",
+            Rank::Seven => "Seven",
+            Rank::Eight => "Eight",
+            Rank::Nine => "Nine",
+            Rank::Ten => "Ten",
+            Rank::Jack => "Jack",
+            Rank::Queen => "Queen",
+            Rank::King => "King",
+        }
+    }
+
+    pub const fn from_int(i: i8) -> Option<Self> {
         match i {
             14 => Some(Rank::Ace),
             2 => Some(Rank::Two),
@@ -146,7 +221,12 @@ impl Rank {
         }
     }
 
-    pub fn as_int(&self) -> i8 {
+    /// Like `from_int`, but reports the offending value as an error.
+    pub fn try_from_int(i: i8) -> Result<Self> {
+        Self::from_int(i).ok_or(Error::InvalidRankInt(i))
+    }
+
+    pub const fn as_int(&self) -> i8 {
         match *self {
             Rank::Two => 2,
             Rank::Three => 3,
@@ -186,20 +266,134 @@ pub struct Card {
 }
 
 impl Card {
-    pub fn new(suit: Suit, rank: Rank) -> Self {
+    pub const fn new(suit: Suit, rank: Rank) -> Self {
         Self { suit, rank }
     }
 
     pub fn try_from_str(str: &str) -> Result<Self> {
-        let mut chars = str.chars();
-        let suit_char = chars.next().ok_or(Error::UnexpectedCardChar)?;
-        let rank_char = chars.next().ok_or(Error::UnexpectedCardChar)?;
-        Ok(Self {
-            suit: Suit::from_char(suit_char).ok_or(Error::UnexpectedCardChar)?,
-            rank: Rank::from_char(rank_char).ok_or(Error::UnexpectedCardChar)?,
+        let mut chars = str.trim().chars();
+        let first = chars.next().ok_or(Error::UnexpectedCardChar)?;
+        let second = chars.next().ok_or(Error::UnexpectedCardChar)?;
+        let card = Self::from_chars(first, second).ok_or(Error::UnexpectedCardChar)?;
+        if chars.next().is_some() {
+            return Err(Error::UnparsedCharsRemaining);
+        }
+        Ok(card)
+    }
+
+    /// Builds a card from its two characters, either suit first (`SA`, `♠A`)
+    /// or rank first (`AS`, `A♠`). No character is both a suit and a rank,
+    /// so the order is never ambiguous.
+    pub(crate) fn from_chars(first: char, second: char) -> Option<Self> {
+        if let (Some(suit), Some(rank)) = (Suit::from_char(first), Rank::from_char(second)) {
+            return Some(Self { suit, rank });
+        }
+        Some(Self {
+            rank: Rank::from_char(first)?,
+            suit: Suit::from_char(second)?,
         })
     }
 
+    /// All 13 cards of the suit.
+    pub fn iter_suit(suit: Suit) -> impl Iterator<Item = Card> {
+        Rank::ranks()
+            .into_iter()
+            .map(move |rank| Card::new(suit.clone(), rank))
+    }
+
+    /// All 4 cards of the rank.
+    pub fn iter_rank(rank: Rank) -> impl Iterator<Item = Card> {
+        Suit::suits()
+            .into_iter()
+            .map(move |suit| Card::new(suit, rank.clone()))
+    }
+
+    /// Packs the card into one byte: the rank (`0` for two up to `12` for
+    /// ace) in the low 4 bits and the suit (`0` clubs, `1` diamonds,
+    /// `2` hearts, `3` spades) in the high 2 bits.
+    ///
+    /// ```text
+    /// bit  7 6 5 4 3 2 1 0
+    ///      s s 0 0 r r r r
+    /// ```
+    pub const fn pack(&self) -> u8 {
+        self.suit.index() << 6 | self.rank_index()
+    }
+
+    /// Reverses `pack`, returning `None` for bytes no card packs into.
+    pub const fn unpack(byte: u8) -> Option<Self> {
+        if byte & 0b0011_0000 != 0 {
+            return None;
+        }
+        let (Some(suit), Some(rank)) = (
+            Suit::from_index(byte >> 6),
+            Rank::from_int((byte & 0x0F) as i8 + 2),
+        ) else {
+            return None;
+        };
+        Some(Self { suit, rank })
+    }
+
+    /// Index of the card in `0..52`, `suit * 13 + rank`, so indices follow
+    /// the card ordering: the two of clubs is `0`, the ace of spades `51`.
+    pub const fn to_index(&self) -> u8 {
+        self.suit.index() * 13 + self.rank_index()
+    }
+
+    /// Reverses `to_index`, returning `None` for indices of 52 and above.
+    pub const fn from_index(index: u8) -> Option<Self> {
+        let (Some(suit), Some(rank)) = (
+            Suit::from_index(index / 13),
+            Rank::from_int((index % 13) as i8 + 2),
+        ) else {
+            return None;
+        };
+        Some(Self { suit, rank })
+    }
+
+    /// The rank from `0` for a two up to `12` for an ace.
+    const fn rank_index(&self) -> u8 {
+        (self.rank.as_int() - 2) as u8
+    }
+
+    /// Compact rank-then-suit notation, e.g. `As` or `Td`.
+    pub fn to_notation(&self) -> String {
+        format!(
+            "{}{}",
+            self.rank.as_char(),
+            self.suit.as_char().to_ascii_lowercase()
+        )
+    }
+
+    /// Like the derived `Ord`, by suit and then rank, but with the ace
+    /// below the two, as in a wheel.
+    pub fn cmp_ace_low(&self, other: &Card) -> Ordering {
+        let ace_low = |c: &Card| (c.rank.clone() as u8 + 1) % 13;
+        (&self.suit, ace_low(self)).cmp(&(&other.suit, ace_low(other)))
+    }
+
+    /// Draws the card as a 5 line, 7 column box for terminal output:
+    ///
+    /// ```text
+    /// +-----+
+    /// |A    |
+    /// |  ♠  |
+    /// |    A|
+    /// +-----+
+    /// ```
+    pub fn render_ascii(&self) -> String {
+        let rank = self.rank.as_char();
+        let suit = self.suit.as_icon_char();
+        [
+            "+-----+".to_string(),
+            format!("|{}    |", rank),
+            format!("|  {}  |", suit),
+            format!("|    {}|", rank),
+            "+-----+".to_string(),
+        ]
+        .join("\n")
+    }
+
     pub fn suit(&self) -> &Suit {
         &self.suit
     }
@@ -209,6 +403,36 @@ impl Card {
     }
 }
 
+/// Parses every string with `Card::try_from_str` into `out`, clearing it
+/// first, so one buffer can be reused across many parses. On error `out`
+/// holds the cards parsed before the bad one.
+pub fn parse_cards_into(strs: &[&str], out: &mut Vec<Card>) -> Result<()> {
+    out.clear();
+    out.reserve(strs.len());
+    for s in strs {
+        out.push(Card::try_from_str(s)?);
+    }
+    Ok(())
+}
+
+/// Height in lines of a card drawn by `Card::render_ascii`.
+const CARD_ART_LINES: usize = 5;
+
+/// Draws several cards side by side, one space apart.
+pub fn render_row(cards: &[Card]) -> String {
+    let rendered: Vec<String> = cards.iter().map(Card::render_ascii).collect();
+    (0..CARD_ART_LINES)
+        .map(|i| {
+            rendered
+                .iter()
+                .map(|card| card.lines().nth(i).unwrap_or_default())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 impl fmt::Display for Card {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}{}", self.suit.as_icon_char(), self.rank.as_char())
@@ -285,6 +509,83 @@ mod tests {
         assert_eq!(expected, Card::try_from_str("SA").unwrap())
     }
 
+    #[test]
+    fn test_try_parse_card_whitespace() {
+        let expected = Card::new(Suit::Heart, Rank::Ten);
+        assert_eq!(expected, Card::try_from_str("HT\n").unwrap());
+        assert_eq!(expected, Card::try_from_str("  ht ").unwrap());
+    }
+
+    #[test]
+    fn test_try_parse_card_trailing_chars() {
+        assert!(matches!(
+            Card::try_from_str("SAxyz"),
+            Err(Error::UnparsedCharsRemaining)
+        ));
+        assert!(Card::try_from_str("SA S").is_err());
+    }
+
+    #[test]
+    fn test_try_parse_card_notations() {
+        let expected = Card::new(Suit::Spade, Rank::Ace);
+        for s in ["SA", "sa", "AS", "As", "♠A", "A♠", "♤a"] {
+            assert_eq!(expected, Card::try_from_str(s).unwrap(), "parsing {}", s);
+        }
+        assert!(Card::try_from_str("AK").is_err());
+        assert!(Card::try_from_str("S♠").is_err());
+    }
+
+    #[test]
+    fn test_index_round_trip() {
+        for i in 0..52 {
+            assert_eq!(i, Card::from_index(i).unwrap().to_index());
+        }
+        assert_eq!(0, Card::try_from_str("2c").unwrap().to_index());
+        assert_eq!(51, Card::try_from_str("As").unwrap().to_index());
+        assert_eq!(None, Card::from_index(52));
+    }
+
+    #[test]
+    fn test_render_ascii() {
+        let card = Card::try_from_str("Ts").unwrap();
+        let art = card.render_ascii();
+        assert_eq!(CARD_ART_LINES, art.lines().count());
+        assert!(art.lines().all(|l| l.chars().count() == 7));
+        assert_eq!(2, art.matches('T').count());
+        assert!(art.contains('♠'));
+
+        let cards = [card, Card::try_from_str("Ah").unwrap()];
+        let row = render_row(&cards);
+        assert_eq!(CARD_ART_LINES, row.lines().count());
+        assert!(row.lines().all(|l| l.chars().count() == 15));
+        assert!(row.contains('♥') && row.contains('A'));
+        assert_eq!("", render_row(&[]).trim());
+    }
+
+    #[test]
+    fn test_cmp_ace_low() {
+        let card = |s: &str| Card::try_from_str(s).unwrap();
+        assert_eq!(Ordering::Less, card("As").cmp_ace_low(&card("2s")));
+        assert_eq!(Ordering::Greater, card("As").cmp(&card("2s")));
+        assert_eq!(Ordering::Greater, card("Ks").cmp_ace_low(&card("As")));
+        assert_eq!(Ordering::Less, card("Kh").cmp_ace_low(&card("As")));
+
+        let mut cards = [card("5d"), card("Ad"), card("3d"), card("2d"), card("4d")];
+        cards.sort_by(Card::cmp_ace_low);
+        let sorted: Vec<String> = cards.iter().map(|c| c.to_string()).collect();
+        assert_eq!(vec!["♦A", "♦2", "♦3", "♦4", "♦5"], sorted);
+    }
+
+    #[test]
+    fn test_try_from_int() {
+        assert_eq!(Rank::Ace, Rank::try_from_int(14).unwrap());
+        assert!(matches!(
+            Rank::try_from_int(15),
+            Err(Error::InvalidRankInt(15))
+        ));
+        assert!(Rank::try_from_int(1).is_err());
+    }
+
     #[test]
     fn test_rank_cmp() {
         assert!(Rank::Two < Rank::Ace);
@@ -298,4 +599,100 @@ mod tests {
         assert!(Suit::Heart < Suit::Spade);
         assert_eq!(Suit::Diamond, Suit::Diamond);
     }
+
+    #[test]
+    fn test_iter_suit() {
+        let hearts: Vec<Card> = Card::iter_suit(Suit::Heart).collect();
+        assert_eq!(13, hearts.len());
+        assert!(hearts.iter().all(|c| c.suit() == &Suit::Heart));
+        assert!(hearts.contains(&Card::new(Suit::Heart, Rank::Seven)));
+    }
+
+    #[test]
+    fn test_iter_rank() {
+        let aces: Vec<Card> = Card::iter_rank(Rank::Ace).collect();
+        assert_eq!(4, aces.len());
+        assert!(aces.iter().all(|c| c.rank() == &Rank::Ace));
+        for suit in Suit::suits() {
+            assert!(aces.contains(&Card::new(suit, Rank::Ace)));
+        }
+    }
+
+    #[test]
+    fn test_pack_round_trip() {
+        for suit in Suit::suits() {
+            for rank in Rank::ranks() {
+                let card = Card::new(suit.clone(), rank);
+                assert_eq!(Some(card.clone()), Card::unpack(card.pack()));
+            }
+        }
+        assert_eq!(0b1100_1100, Card::new(Suit::Spade, Rank::Ace).pack());
+        assert_eq!(0b0000_0000, Card::new(Suit::Club, Rank::Two).pack());
+    }
+
+    #[test]
+    fn test_unpack_invalid() {
+        assert_eq!(None, Card::unpack(0b0000_1101));
+        assert_eq!(None, Card::unpack(0b0001_0000));
+    }
+
+    #[test]
+    fn test_iter_in_order() {
+        let derived: Vec<Suit> = Suit::iter_in_order(SuitOrder::Derived).collect();
+        assert_eq!(
+            vec![Suit::Club, Suit::Diamond, Suit::Heart, Suit::Spade],
+            derived
+        );
+        let bridge: Vec<Suit> = Suit::iter_in_order(SuitOrder::Bridge).collect();
+        assert_eq!(
+            vec![Suit::Spade, Suit::Heart, Suit::Diamond, Suit::Club],
+            bridge
+        );
+        assert_eq!(
+            derived,
+            Suit::iter_in_order(SuitOrder::default()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_parse_cards_into() {
+        let mut buffer = Vec::new();
+        parse_cards_into(&["As", "Kd", "2c"], &mut buffer).unwrap();
+        assert_eq!(
+            vec![
+                Card::new(Suit::Spade, Rank::Ace),
+                Card::new(Suit::Diamond, Rank::King),
+                Card::new(Suit::Club, Rank::Two),
+            ],
+            buffer
+        );
+        let capacity = buffer.capacity();
+
+        parse_cards_into(&["Th"], &mut buffer).unwrap();
+        assert_eq!(vec![Card::new(Suit::Heart, Rank::Ten)], buffer);
+        assert_eq!(capacity, buffer.capacity());
+
+        assert!(parse_cards_into(&["9s", "Xx"], &mut buffer).is_err());
+        assert_eq!(vec![Card::new(Suit::Spade, Rank::Nine)], buffer);
+    }
+
+    #[test]
+    fn test_const_construction() {
+        const BROADWAY: [Card; 2] = [
+            Card::new(Suit::Spade, Rank::Ace),
+            Card::new(Suit::Heart, Rank::King),
+        ];
+        const ACE_INDEX: u8 = BROADWAY[0].to_index();
+        const LAST: Option<Card> = Card::from_index(51);
+        const PACKED: Option<Card> = Card::unpack(BROADWAY[1].pack());
+        const TEN: Option<Rank> = Rank::from_char('t');
+        const HEART: Option<Suit> = Suit::from_char('h');
+
+        assert_eq!(51, ACE_INDEX);
+        assert_eq!(Some(BROADWAY[0].clone()), LAST);
+        assert_eq!(Some(BROADWAY[1].clone()), PACKED);
+        assert_eq!(Some(Rank::Ten), TEN);
+        assert_eq!(Some(Suit::Heart), HEART);
+        assert_eq!(None, Suit::from_index(4));
+    }
 }
@@ -1,27 +1,61 @@
 use std::{
-    collections::{hash_set::Iter, HashSet},
+    collections::{btree_set::Iter, BTreeSet},
     fmt,
 };
 
+use rand::{
+    rngs::StdRng,
+    seq::{IteratorRandom, SliceRandom},
+    Rng, SeedableRng,
+};
+
 use super::card::{Card, Rank, Suit};
+use crate::error::{Error, Result};
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, Clone)]
+/// A set of cards kept in canonical order: by suit (clubs, diamonds,
+/// hearts, spades) and then by rank from two to ace.
+#[derive(PartialEq, Eq, Debug, Clone)]
 pub struct Deck {
-    cards: HashSet<Card>,
+    cards: BTreeSet<Card>,
 }
 
 impl Deck {
     pub fn new() -> Self {
         Self {
-            cards: HashSet::new(),
+            cards: BTreeSet::new(),
         }
     }
 
+    /// A full 52 card deck, the same as `Deck::default()`.
+    pub fn standard() -> Self {
+        Self::default()
+    }
+
+    /// A full deck shuffled with `seed`, as the cards in deal order. The
+    /// deck itself always keeps its cards in canonical order, so the
+    /// shuffle is returned as a list.
+    pub fn shuffled_cards(seed: u64) -> Vec<Card> {
+        let mut cards = Self::standard().get_all_cards();
+        cards.shuffle(&mut StdRng::seed_from_u64(seed));
+        cards
+    }
+
     pub fn insert(&mut self, c: Card) -> bool {
         self.cards.insert(c)
     }
 
+    /// Like `insert`, but reports a card already in the deck as an error.
+    /// Every `Card` is one of the 52 standard cards, so a deck built this
+    /// way never holds more than a standard deck.
+    pub fn try_insert(&mut self, c: Card) -> Result<()> {
+        if self.cards.contains(&c) {
+            return Err(Error::DuplicateCardInDeck(c));
+        }
+        self.cards.insert(c);
+        Ok(())
+    }
+
     pub fn remove(&mut self, c: &Card) -> bool {
         self.cards.remove(c)
     }
@@ -38,7 +72,27 @@ impl Deck {
         self.cards.contains(c)
     }
 
-    pub fn iter(&self) -> Iter<Card> {
+    /// Whether every card of this deck is also in `other`.
+    pub fn is_subset_of(&self, other: &Deck) -> bool {
+        self.cards.is_subset(&other.cards)
+    }
+
+    /// Whether any card of the rank is left in the deck.
+    pub fn has_rank(&self, r: Rank) -> bool {
+        Suit::suits()
+            .into_iter()
+            .any(|s| self.cards.contains(&Card::new(s, r.clone())))
+    }
+
+    /// How many cards of the suit are left in the deck.
+    pub fn has_suit_count(&self, s: Suit) -> usize {
+        // Cards are ordered by suit first, so a suit is one contiguous range.
+        let lowest = Card::new(s.clone(), Rank::Two);
+        let highest = Card::new(s, Rank::Ace);
+        self.cards.range(lowest..=highest).count()
+    }
+
+    pub fn iter(&self) -> Iter<'_, Card> {
         self.cards.iter()
     }
 
@@ -46,6 +100,52 @@ impl Deck {
         self.cards.iter().cloned().collect()
     }
 
+    /// Returns a new deck with the given cards removed, leaving this one untouched.
+    pub fn without(&self, cards: &[Card]) -> Deck {
+        let mut deck = self.clone();
+        for c in cards {
+            deck.remove(c);
+        }
+        deck
+    }
+
+    /// Returns the remaining cards as bits, see `Hand::to_bitset`.
+    pub fn to_bitset(&self) -> u64 {
+        self.cards
+            .iter()
+            .fold(0, |bits, c| bits | 1 << c.to_index())
+    }
+
+    /// Builds a deck holding exactly the cards set in `bits`.
+    pub fn from_bitset(bits: u64) -> Result<Deck> {
+        if bits >> 52 != 0 {
+            return Err(Error::InvalidCardBitset(bits));
+        }
+        Ok(Deck {
+            cards: (0..52)
+                .filter(|i| bits & 1 << i != 0)
+                .filter_map(Card::from_index)
+                .collect(),
+        })
+    }
+
+    /// Samples `n` random cards without removing them, fewer if the deck
+    /// runs out.
+    pub fn choose_multiple(&self, n: usize, rng: &mut impl Rng) -> Vec<Card> {
+        self.cards.iter().cloned().choose_multiple(rng, n)
+    }
+
+    /// Removes and returns a uniformly random card from the deck.
+    pub fn draw_random(&mut self, rng: &mut impl Rng) -> Option<Card> {
+        if self.cards.is_empty() {
+            return None;
+        }
+        let i = rng.gen_range(0..self.cards.len());
+        let card = self.cards.iter().nth(i)?.clone();
+        self.cards.remove(&card);
+        Some(card)
+    }
+
     pub fn deal_card(&mut self) -> Option<Card> {
         if let Some(card) = self.cards.iter().next().cloned() {
             self.cards.remove(&card);
@@ -54,11 +154,26 @@ impl Deck {
             None
         }
     }
+
+    /// Puts back a dealt card, returning `false` if it was never dealt.
+    /// The deck keeps its canonical order, so a card just taken by
+    /// `deal_card` goes back on top and is dealt again next.
+    pub fn return_card(&mut self, c: Card) -> bool {
+        self.cards.insert(c)
+    }
+
+    /// Like `deal_card`, but skips over the `dead` cards, which stay in the
+    /// deck.
+    pub fn deal_excluding(&mut self, dead: &[Card]) -> Option<Card> {
+        let card = self.cards.iter().find(|c| !dead.contains(c))?.clone();
+        self.cards.remove(&card);
+        Some(card)
+    }
 }
 
 impl Default for Deck {
     fn default() -> Self {
-        let mut cards: HashSet<Card> = HashSet::new();
+        let mut cards: BTreeSet<Card> = BTreeSet::new();
         for s in &Suit::suits() {
             for r in &Rank::ranks() {
                 cards.insert(Card::new(s.clone(), r.clone()));
@@ -68,6 +183,52 @@ impl Default for Deck {
     }
 }
 
+/// Builds stripped or custom decks, such as a short deck without the
+/// twos to fives.
+#[derive(Debug, Clone)]
+pub struct DeckBuilder {
+    ranks: Vec<Rank>,
+    suits: Vec<Suit>,
+}
+
+impl DeckBuilder {
+    /// Starts from the full 52 card deck.
+    pub fn new() -> Self {
+        Self {
+            ranks: Rank::ranks().to_vec(),
+            suits: Suit::suits().to_vec(),
+        }
+    }
+
+    /// Leaves out every card of the given ranks.
+    pub fn without_ranks(mut self, ranks: &[Rank]) -> Self {
+        self.ranks.retain(|r| !ranks.contains(r));
+        self
+    }
+
+    /// Keeps only the cards of the given suits.
+    pub fn only_suits(mut self, suits: &[Suit]) -> Self {
+        self.suits.retain(|s| suits.contains(s));
+        self
+    }
+
+    pub fn build(&self) -> Deck {
+        let mut deck = Deck::new();
+        for s in &self.suits {
+            for r in &self.ranks {
+                deck.insert(Card::new(s.clone(), r.clone()));
+            }
+        }
+        deck
+    }
+}
+
+impl Default for DeckBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl fmt::Display for Deck {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let cards_vec: Vec<&Card> = self.cards.iter().collect();
@@ -88,6 +249,7 @@ impl fmt::Display for Deck {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
 
     #[test]
     fn test_contains_in() {
@@ -104,4 +266,201 @@ mod tests {
         assert!(!d.contains(&c));
         assert!(!d.remove(&c));
     }
+
+    #[test]
+    fn test_without() {
+        let d = Deck::default();
+        let known = [
+            Card::new(Suit::Spade, Rank::Ace),
+            Card::new(Suit::Heart, Rank::King),
+        ];
+        let remaining = d.without(&known);
+        assert_eq!(50, remaining.len());
+        assert!(!remaining.contains(&known[0]));
+        assert!(!remaining.contains(&known[1]));
+        assert_eq!(52, d.len());
+        assert!(d.contains(&known[0]));
+    }
+
+    #[test]
+    fn test_draw_random() {
+        let mut d = Deck::default();
+        let mut rng = StdRng::seed_from_u64(9);
+        let card = d.draw_random(&mut rng).unwrap();
+        assert_eq!(51, d.len());
+        assert!(!d.contains(&card));
+
+        let mut other = Deck::default();
+        let mut rng = StdRng::seed_from_u64(9);
+        assert_eq!(Some(card), other.draw_random(&mut rng));
+
+        let mut empty = Deck::new();
+        assert_eq!(None, empty.draw_random(&mut rng));
+    }
+
+    #[test]
+    fn test_standard_order() {
+        let d = Deck::standard();
+        let first: Vec<Card> = d.iter().take(3).cloned().collect();
+        assert_eq!(
+            vec![
+                Card::new(Suit::Club, Rank::Two),
+                Card::new(Suit::Club, Rank::Three),
+                Card::new(Suit::Club, Rank::Four),
+            ],
+            first
+        );
+        assert_eq!(
+            Some(&Card::new(Suit::Spade, Rank::Ace)),
+            d.iter().next_back()
+        );
+        assert!(d.to_string().starts_with("♣2, ♣3, ♣4"));
+        assert_eq!(d.to_string(), Deck::default().to_string());
+    }
+
+    #[test]
+    fn test_bitset_round_trip() {
+        let deck = Deck::standard();
+        assert_eq!((1 << 52) - 1, deck.to_bitset());
+        let deck = deck.without(&[Card::try_from_str("2c").unwrap()]);
+        assert_eq!((1 << 52) - 2, deck.to_bitset());
+        let back = Deck::from_bitset(deck.to_bitset()).unwrap();
+        assert_eq!(deck.get_all_cards(), back.get_all_cards());
+        assert!(Deck::from_bitset(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_has_rank_and_suit_count() {
+        let mut deck = Deck::standard();
+        assert_eq!(13, deck.has_suit_count(Suit::Heart));
+        for c in Card::iter_suit(Suit::Heart) {
+            deck.remove(&c);
+        }
+        assert_eq!(0, deck.has_suit_count(Suit::Heart));
+        assert_eq!(13, deck.has_suit_count(Suit::Spade));
+        assert!(deck.has_rank(Rank::Ace));
+
+        for c in Card::iter_rank(Rank::Ace) {
+            deck.remove(&c);
+        }
+        assert!(!deck.has_rank(Rank::Ace));
+        assert!(deck.has_rank(Rank::King));
+        assert_eq!(12, deck.has_suit_count(Suit::Club));
+    }
+
+    #[test]
+    fn test_deal_excluding() {
+        let mut deck = Deck::standard();
+        let dead: Vec<Card> = Card::iter_suit(Suit::Club).collect();
+        for _ in 0..39 {
+            let card = deck.deal_excluding(&dead).unwrap();
+            assert!(!dead.contains(&card));
+        }
+        assert_eq!(None, deck.deal_excluding(&dead));
+        assert_eq!(13, deck.len());
+        assert_eq!(13, deck.has_suit_count(Suit::Club));
+    }
+
+    #[test]
+    fn test_shuffled_cards() {
+        let first = Deck::shuffled_cards(451);
+        assert_eq!(first, Deck::shuffled_cards(451));
+        assert_ne!(first, Deck::shuffled_cards(452));
+        assert_ne!(first, Deck::standard().get_all_cards());
+
+        let mut sorted = first.clone();
+        sorted.sort();
+        assert_eq!(Deck::standard().get_all_cards(), sorted);
+    }
+
+    #[test]
+    fn test_choose_multiple() {
+        let deck = Deck::standard();
+        let sample = deck.choose_multiple(5, &mut StdRng::seed_from_u64(454));
+        assert_eq!(5, sample.len());
+        assert_eq!(
+            sample,
+            deck.choose_multiple(5, &mut StdRng::seed_from_u64(454))
+        );
+        assert_eq!(52, deck.len());
+        assert!(sample.iter().all(|c| deck.contains(c)));
+
+        let small = Deck::standard()
+            .without(&sample[1..])
+            .without(&deck.get_all_cards()[..]);
+        assert!(small
+            .choose_multiple(3, &mut StdRng::seed_from_u64(1))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_try_insert() {
+        let mut deck = Deck::new();
+        for c in Deck::standard().iter() {
+            deck.try_insert(c.clone()).unwrap();
+        }
+        assert_eq!(52, deck.len());
+
+        let ace = Card::try_from_str("SA").unwrap();
+        assert!(matches!(
+            deck.try_insert(ace.clone()),
+            Err(Error::DuplicateCardInDeck(c)) if c == ace
+        ));
+        assert_eq!(52, deck.len());
+    }
+
+    #[test]
+    fn test_return_card() {
+        let mut deck = Deck::standard();
+        let first = deck.deal_card().unwrap();
+        let second = deck.deal_card().unwrap();
+        assert_eq!(50, deck.len());
+
+        assert!(deck.return_card(second.clone()));
+        assert!(!deck.return_card(second.clone()));
+        assert!(deck.return_card(first.clone()));
+        assert_eq!(52, deck.len());
+        assert_eq!(Some(first), deck.deal_card());
+        assert_eq!(Some(second), deck.deal_card());
+    }
+
+    #[test]
+    fn test_deck_builder() {
+        let short = DeckBuilder::new()
+            .without_ranks(&[Rank::Two, Rank::Three, Rank::Four, Rank::Five])
+            .build();
+        assert_eq!(36, short.len());
+        assert!(!short.has_rank(Rank::Five));
+        assert!(short.has_rank(Rank::Six));
+
+        let two_suits = DeckBuilder::new()
+            .only_suits(&[Suit::Spade, Suit::Heart])
+            .build();
+        assert_eq!(26, two_suits.len());
+        assert_eq!(0, two_suits.has_suit_count(Suit::Club));
+        assert_eq!(13, two_suits.has_suit_count(Suit::Heart));
+
+        assert_eq!(
+            Deck::standard().get_all_cards(),
+            DeckBuilder::default().build().get_all_cards()
+        );
+    }
+
+    #[test]
+    fn test_equality_and_subset() {
+        let full = Deck::standard();
+        assert_eq!(full, Deck::default());
+        assert_eq!(full, DeckBuilder::new().build());
+
+        let mut dealt = full.clone();
+        let card = dealt.deal_card().unwrap();
+        assert_ne!(full, dealt);
+        assert!(dealt.is_subset_of(&full));
+        assert!(!full.is_subset_of(&dealt));
+        assert!(Deck::new().is_subset_of(&dealt));
+        assert!(full.is_subset_of(&full));
+
+        dealt.return_card(card);
+        assert_eq!(full, dealt);
+    }
 }
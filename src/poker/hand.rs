@@ -1,61 +1,191 @@
 use std::{fmt, slice::Iter};
 
-use crate::error::Result;
+use crate::error::{Error, Result};
 
-use super::card::Card;
+use once_cell::sync::OnceCell;
+
+use super::{
+    card::{parse_cards_into, Card},
+    rank::{HandRanker, Rank},
+};
 
 #[derive(Debug)]
-pub struct Hand(Vec<Card>);
+pub struct Hand {
+    cards: Vec<Card>,
+    /// The result of `rank()`, cleared whenever the cards change.
+    rank: OnceCell<Rank>,
+}
 
 impl Hand {
     pub fn new_with_cards(cards: Vec<Card>) -> Self {
-        Self(cards)
+        Self {
+            cards,
+            rank: OnceCell::new(),
+        }
     }
 
     pub fn new_from_strs(strs: &[&str]) -> Result<Self> {
         let mut cards = Vec::new();
-        for s in strs {
-            let card = Card::try_from_str(s)?;
-            cards.push(card)
+        parse_cards_into(strs, &mut cards)?;
+        Ok(Self::new_with_cards(cards))
+    }
+
+    /// Parses a string of cards that may mix notations, such as
+    /// `"♠A KH, d9 Tc 2♥"`. Whitespace and commas between cards are optional.
+    pub fn from_mixed_notation(s: &str) -> Result<Self> {
+        let chars: Vec<char> = s
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != ',')
+            .collect();
+        if !chars.len().is_multiple_of(2) {
+            return Err(Error::UnexpectedCardChar);
         }
-        Ok(Self(cards))
+        let cards = chars
+            .chunks(2)
+            .map(|pair| Card::from_chars(pair[0], pair[1]).ok_or(Error::UnexpectedCardChar))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::new_with_cards(cards))
+    }
+
+    /// The cards in compact notation with no separators, e.g.
+    /// `AsKsQsJsTs`, which `from_mixed_notation` parses back.
+    pub fn to_notation(&self) -> String {
+        self.cards.iter().map(Card::to_notation).collect()
     }
 
     pub fn cards(&self) -> &[Card] {
-        &self.0
+        &self.cards
     }
 
     pub fn len(&self) -> usize {
-        self.0.len()
+        self.cards.len()
     }
 
     pub fn push(&mut self, c: Card) -> &mut Self {
-        self.0.push(c);
+        self.cards.push(c);
+        self.rank.take();
         self
     }
 
     pub fn remove(&mut self, len: usize) -> &mut Self {
-        self.0.remove(len);
+        self.cards.remove(len);
+        self.rank.take();
         self
     }
 
     pub fn truncate(&mut self, len: usize) -> &mut Self {
-        self.0.truncate(len);
+        self.cards.truncate(len);
+        self.rank.take();
         self
     }
 
+    /// Removes and returns the card at `idx`, e.g. a discard in a draw
+    /// game. Panics if `idx` is out of bounds, like `Vec::remove`.
+    pub fn take(&mut self, idx: usize) -> Card {
+        self.rank.take();
+        self.cards.remove(idx)
+    }
+
+    /// Keeps the first `len` cards and returns the rest.
+    pub fn truncate_returning(&mut self, len: usize) -> Vec<Card> {
+        if len >= self.cards.len() {
+            return Vec::new();
+        }
+        self.rank.take();
+        self.cards.split_off(len)
+    }
+
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.cards.is_empty()
+    }
+
+    /// Same as `rank()`, but only computed once until the hand changes.
+    pub fn cached_rank(&self) -> Rank {
+        self.rank.get_or_init(|| self.rank()).clone()
+    }
+
+    /// Like `rank()`, but `None` for hands of fewer than five cards.
+    pub fn try_rank(&self) -> Option<Rank> {
+        (self.len() >= 5).then(|| self.rank())
     }
 
-    pub fn iter(&self) -> Iter<Card> {
-        self.0.iter()
+    /// Returns a copy of the hand with the cards appended, rejecting
+    /// duplicates and hands of more than 7 cards.
+    pub fn with_added(&self, cards: &[Card]) -> Result<Hand> {
+        if self.len() + cards.len() > 7 {
+            return Err(Error::HoldemHandSize);
+        }
+        let mut hand = Self::new_with_cards(self.cards.clone());
+        for c in cards {
+            if hand.cards.contains(c) {
+                return Err(Error::DuplicateCardInHand(c.clone()));
+            }
+            hand.cards.push(c.clone());
+        }
+        Ok(hand)
+    }
+
+    /// The first `n` cards, the hole cards of a hand stored hole then board.
+    pub fn hole(&self, n: usize) -> &[Card] {
+        &self.cards[..n.min(self.cards.len())]
+    }
+
+    /// The cards after the first `n`, the board of a hand stored hole then
+    /// board.
+    pub fn board(&self, n: usize) -> &[Card] {
+        &self.cards[n.min(self.cards.len())..]
+    }
+
+    /// Splits a 7 card hand back into its 2 hole cards and 5 board cards,
+    /// assuming the hole cards come first.
+    pub fn split_hole_board(&self) -> Result<([Card; 2], [Card; 5])> {
+        if self.len() != 7 {
+            return Err(Error::IncompleteHoldemHand);
+        }
+        let hole = [self.cards[0].clone(), self.cards[1].clone()];
+        let board = [
+            self.cards[2].clone(),
+            self.cards[3].clone(),
+            self.cards[4].clone(),
+            self.cards[5].clone(),
+            self.cards[6].clone(),
+        ];
+        Ok((hole, board))
+    }
+
+    /// Returns the cards as a set of bits, bit `Card::to_index` for each.
+    pub fn to_bitset(&self) -> u64 {
+        self.cards
+            .iter()
+            .fold(0, |bits, c| bits | 1 << c.to_index())
+    }
+
+    /// Builds a hand from `to_bitset` bits, cards in index order.
+    pub fn from_bitset(bits: u64) -> Result<Hand> {
+        if bits >> 52 != 0 {
+            return Err(Error::InvalidCardBitset(bits));
+        }
+        let cards = (0..52)
+            .filter(|i| bits & 1 << i != 0)
+            .filter_map(Card::from_index)
+            .collect();
+        Ok(Self::new_with_cards(cards))
+    }
+
+    pub fn iter(&self) -> Iter<'_, Card> {
+        self.cards.iter()
+    }
+}
+
+impl AsRef<[Card]> for Hand {
+    fn as_ref(&self) -> &[Card] {
+        &self.cards
     }
 }
 
 // impl Extend<Card> for Hand {
 //     fn extend<T: IntoIterator<Item = Card>>(&mut self, iter: T) {
-//         self.0.extend(iter);
+//         self.cards.extend(iter);
 //     }
 // }
 
@@ -87,4 +217,142 @@ mod tests {
         let hand2 = vec!["sa", "sx"];
         assert!(Hand::new_from_strs(&hand2).is_err());
     }
+
+    #[test]
+    fn test_split_hole_board() {
+        let hole = Hand::new_from_strs(&["sa", "hk"]).unwrap();
+        let board = Hand::new_from_strs(&["d2", "c7", "s9", "hj", "dq"]).unwrap();
+        let mut cards = hole.cards().to_vec();
+        cards.extend_from_slice(board.cards());
+
+        let (h, b) = Hand::new_with_cards(cards).split_hole_board().unwrap();
+        assert_eq!(hole.cards(), &h);
+        assert_eq!(board.cards(), &b);
+
+        assert!(hole.split_hole_board().is_err());
+    }
+
+    #[test]
+    fn test_with_added() {
+        let hole = Hand::new_from_strs(&["sa", "hk"]).unwrap();
+        let board = Hand::new_from_strs(&["d2", "c7", "s9"]).unwrap();
+        let hand = hole.with_added(board.cards()).unwrap();
+        assert_eq!(5, hand.len());
+        assert_eq!(2, hole.len());
+        assert_eq!(&hand.cards()[2..], board.cards());
+
+        let dup = Hand::new_from_strs(&["d2", "sa"]).unwrap();
+        assert!(matches!(
+            hole.with_added(dup.cards()),
+            Err(Error::DuplicateCardInHand(_))
+        ));
+
+        let river = Hand::new_from_strs(&["d2", "c7", "s9", "hj", "dq", "c3"]).unwrap();
+        assert!(matches!(
+            hole.with_added(river.cards()),
+            Err(Error::HoldemHandSize)
+        ));
+    }
+
+    #[test]
+    fn test_from_mixed_notation() {
+        let expected = Hand::new_from_strs(&["sa", "hk", "d9", "ct", "h2"]).unwrap();
+        let hand = Hand::from_mixed_notation("♠A KH, d9 Tc 2♥").unwrap();
+        assert_eq!(expected.cards(), hand.cards());
+
+        let hand = Hand::from_mixed_notation("♠AKHd9Tc2♡").unwrap();
+        assert_eq!(expected.cards(), hand.cards());
+
+        assert!(Hand::from_mixed_notation("♠A KH d").is_err());
+        assert!(Hand::from_mixed_notation("♠A XH").is_err());
+    }
+
+    #[test]
+    fn test_cached_rank() {
+        let mut hand = Hand::new_from_strs(&["sa", "ha", "d9", "c7", "s2"]).unwrap();
+        assert!(hand.cached_rank().is_one_pair());
+        assert_eq!(hand.rank(), hand.cached_rank());
+
+        hand.push(Card::try_from_str("da").unwrap());
+        assert!(hand.cached_rank().is_three_of_a_kind());
+
+        hand.remove(5);
+        assert!(hand.cached_rank().is_one_pair());
+
+        hand.truncate(1);
+        assert!(hand.cached_rank().is_high_card());
+        assert_eq!(hand.rank(), hand.cached_rank());
+    }
+
+    #[test]
+    fn test_bitset_round_trip() {
+        let hand = Hand::new_from_strs(&["As", "Kd", "2c", "Th", "9h", "3s", "Qc"]).unwrap();
+        let bits = hand.to_bitset();
+        assert_eq!(7, bits.count_ones());
+        for c in hand.iter() {
+            assert_ne!(0, bits & 1 << c.to_index());
+        }
+        assert_eq!(
+            1 << 51 | 1,
+            Hand::new_from_strs(&["As", "2c"]).unwrap().to_bitset()
+        );
+
+        let back = Hand::from_bitset(bits).unwrap();
+        assert_eq!(bits, back.to_bitset());
+        assert_eq!(hand.rank(), back.rank());
+        assert!(matches!(
+            Hand::from_bitset(1 << 52),
+            Err(Error::InvalidCardBitset(_))
+        ));
+    }
+
+    #[test]
+    fn test_take_and_truncate_returning() {
+        let card = |s: &str| Card::try_from_str(s).unwrap();
+        let mut hand = Hand::new_from_strs(&["SA", "HA", "D9", "C7", "S2"]).unwrap();
+        assert!(hand.cached_rank().is_one_pair());
+
+        assert_eq!(card("HA"), hand.take(1));
+        assert_eq!(vec![card("C7"), card("S2")], hand.truncate_returning(2));
+        assert_eq!(&[card("SA"), card("D9")], hand.cards());
+        assert!(hand.cached_rank().is_high_card());
+
+        assert!(hand.truncate_returning(5).is_empty());
+        assert_eq!(2, hand.len());
+    }
+
+    #[test]
+    fn test_to_notation() {
+        let hand = Hand::new_from_strs(&["SA", "HK", "D9", "CT", "S2", "h7", "dq"]).unwrap();
+        let notation = hand.to_notation();
+        assert_eq!("AsKh9dTc2s7hQd", notation);
+        let back = Hand::from_mixed_notation(&notation).unwrap();
+        assert_eq!(hand.cards(), back.cards());
+    }
+
+    #[test]
+    fn test_hole_and_board() {
+        let hand = Hand::new_from_strs(&["SA", "HK", "D9", "CT", "S2", "h7", "dq"]).unwrap();
+        let (hole, board) = hand.split_hole_board().unwrap();
+        assert_eq!(&hole, hand.hole(2));
+        assert_eq!(&board, hand.board(2));
+
+        // Omaha style split.
+        assert_eq!(4, hand.hole(4).len());
+        assert_eq!(3, hand.board(4).len());
+        assert_eq!(7, hand.hole(9).len());
+        assert!(hand.board(9).is_empty());
+    }
+
+    #[test]
+    fn test_try_rank() {
+        let mut hand = Hand::new_from_strs(&["sa", "ha", "dk", "ck"]).unwrap();
+        assert_eq!(None, hand.try_rank());
+        assert_eq!(None, Hand::new_with_cards(vec![]).try_rank());
+
+        hand.push(Card::try_from_str("s2").unwrap());
+        let rank = hand.try_rank().unwrap();
+        assert!(rank.is_two_pair());
+        assert_eq!(hand.rank(), rank);
+    }
 }
@@ -0,0 +1,89 @@
+use std::cmp::Ordering;
+
+use super::card::{Card, Rank};
+
+/// An ace-to-five low hand: five distinct ranks with aces counting as one,
+/// ignoring straights and flushes.
+///
+/// `LowRank`s compare like `Rank`s, so the better (lower) hand is the
+/// greater value and `5-4-3-2-A` beats everything.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub struct LowRank([u8; 5]);
+
+impl LowRank {
+    /// The five card values from highest to lowest, with the ace as `1`.
+    pub fn values(&self) -> [u8; 5] {
+        self.0
+    }
+}
+
+impl Ord for LowRank {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.cmp(&self.0)
+    }
+}
+
+impl PartialOrd for LowRank {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn low_value(rank: &Rank) -> u8 {
+    match rank {
+        Rank::Ace => 1,
+        r => r.as_int() as u8,
+    }
+}
+
+/// Finds the best eight-or-better low in the cards, or `None` when there
+/// aren't five distinct ranks of eight or lower.
+pub fn rank_low_eight_or_better(cards: &[Card]) -> Option<LowRank> {
+    let mut values: Vec<u8> = cards
+        .iter()
+        .map(|c| low_value(c.rank()))
+        .filter(|&v| v <= 8)
+        .collect();
+    values.sort_unstable();
+    values.dedup();
+    if values.len() < 5 {
+        return None;
+    }
+    Some(LowRank([
+        values[4], values[3], values[2], values[1], values[0],
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poker::hand::Hand;
+
+    fn low(strs: &[&str]) -> Option<LowRank> {
+        rank_low_eight_or_better(Hand::new_from_strs(strs).unwrap().cards())
+    }
+
+    #[test]
+    fn test_qualifies() {
+        let rank = low(&["s8", "h6", "d4", "c3", "sa", "hk", "dk"]).unwrap();
+        assert_eq!([8, 6, 4, 3, 1], rank.values());
+    }
+
+    #[test]
+    fn test_does_not_qualify() {
+        assert_eq!(None, low(&["s9", "h6", "d4", "c3", "sa", "hk", "dk"]));
+        assert_eq!(None, low(&["s8", "h8", "d4", "c4", "sa", "ha", "d2"]));
+    }
+
+    #[test]
+    fn test_best_low_chosen() {
+        // Straights and flushes don't count against a low.
+        let rank = low(&["h5", "h4", "h3", "h2", "ha", "s8", "d7"]).unwrap();
+        assert_eq!([5, 4, 3, 2, 1], rank.values());
+
+        let seven = low(&["s7", "h5", "d4", "c3", "s2"]).unwrap();
+        let eight = low(&["s8", "h6", "d4", "c3", "sa"]).unwrap();
+        assert!(seven > eight);
+        assert!(rank > seven);
+    }
+}
@@ -1,4 +1,10 @@
+pub mod batch;
+pub mod cache;
 pub mod card;
 pub mod deck;
 pub mod hand;
+pub mod low;
 pub mod rank;
+pub mod showdown;
+pub mod variant;
+pub mod wild;
@@ -1,9 +1,29 @@
-use super::{card::Card, hand::Hand};
+use std::{cmp::Ordering, fmt};
+
+use once_cell::sync::Lazy;
+use rand::{seq::SliceRandom, Rng};
+
+use super::{
+    card::{self, Card, Suit},
+    deck::Deck,
+    hand::Hand,
+    variant::GameVariant,
+};
 
 /// All the different possible hand ranks.
 /// For each hand rank the u16 corresponds to
 /// the strength of the hand in comparison to others
 /// of the same rank.
+///
+/// High cards and flushes carry a bitmask of their five ranks and
+/// straights carry the index of their lowest card. The paired ranks
+/// pack their cards as 4-bit rank indices, most significant first
+/// (e.g. a full house is `set << 4 | pair`). Hands of fewer than 5 cards
+/// keep the same layout, with zeros for the missing kickers.
+///
+/// The derived `Ord` compares the category (declaration order) first and
+/// only looks at the payload between ranks of the same category, so any
+/// payload of a lower category loses to every payload of a higher one.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Hash, Debug)]
 pub enum Rank {
@@ -19,6 +39,478 @@ pub enum Rank {
     StraightFlush(u16),
 }
 
+/// The hand rank categories without their strength payload.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Hash, Debug)]
+pub enum RankCategory {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+}
+
+/// All of the categories from weakest to strongest.
+const RANK_CATEGORIES: [RankCategory; 9] = [
+    RankCategory::HighCard,
+    RankCategory::OnePair,
+    RankCategory::TwoPair,
+    RankCategory::ThreeOfAKind,
+    RankCategory::Straight,
+    RankCategory::Flush,
+    RankCategory::FullHouse,
+    RankCategory::FourOfAKind,
+    RankCategory::StraightFlush,
+];
+
+/// Number of distinct 5 card hands dealt from a 52 card deck.
+const FIVE_CARD_HANDS: u32 = 2_598_960;
+
+/// How many of the 5 card hands fall in each category, weakest first.
+const CATEGORY_FREQUENCIES: [u32; 9] = [
+    1_302_540, 1_098_240, 123_552, 54_912, 10_200, 5_108, 3_744, 624, 40,
+];
+
+/// How many 5 card hands make each single rank of a category: every suit
+/// pattern that doesn't change the category.
+const CLASS_FREQUENCIES: [u32; 9] = [1020, 384, 144, 64, 1020, 4, 24, 4, 4];
+
+impl RankCategory {
+    pub const fn all() -> [Self; 9] {
+        RANK_CATEGORIES
+    }
+
+    /// Chance that a random 5 card hand is of this category, e.g. about
+    /// 0.00197 for a flush.
+    pub fn probability(&self) -> f64 {
+        CATEGORY_FREQUENCIES[*self as usize] as f64 / FIVE_CARD_HANDS as f64
+    }
+
+    /// The category's name, e.g. "Full House".
+    pub fn name(&self) -> &'static str {
+        match self {
+            RankCategory::HighCard => "High Card",
+            RankCategory::OnePair => "One Pair",
+            RankCategory::TwoPair => "Two Pair",
+            RankCategory::ThreeOfAKind => "Three of a Kind",
+            RankCategory::Straight => "Straight",
+            RankCategory::Flush => "Flush",
+            RankCategory::FullHouse => "Full House",
+            RankCategory::FourOfAKind => "Four of a Kind",
+            RankCategory::StraightFlush => "Straight Flush",
+        }
+    }
+
+    /// The rank with a `0` payload, which is at or below every hand of the
+    /// category, for thresholds like `rank >= TwoPair.into_min_rank()`.
+    pub fn into_min_rank(&self) -> Rank {
+        Rank::from_parts(*self, 0)
+    }
+
+    /// A two character code for compact tables, e.g. "FH".
+    pub fn short_code(&self) -> &'static str {
+        match self {
+            RankCategory::HighCard => "HC",
+            RankCategory::OnePair => "1P",
+            RankCategory::TwoPair => "2P",
+            RankCategory::ThreeOfAKind => "3K",
+            RankCategory::Straight => "ST",
+            RankCategory::Flush => "FL",
+            RankCategory::FullHouse => "FH",
+            RankCategory::FourOfAKind => "4K",
+            RankCategory::StraightFlush => "SF",
+        }
+    }
+
+    /// The name to show for the category in `game`. Ace-to-five lowball
+    /// ignores straights and flushes, so they show as a high card.
+    pub fn display_for(&self, game: GameVariant) -> &'static str {
+        match (game, self) {
+            (
+                GameVariant::AceToFiveLowball,
+                RankCategory::Straight | RankCategory::Flush | RankCategory::StraightFlush,
+            ) => RankCategory::HighCard.name(),
+            _ => self.name(),
+        }
+    }
+
+    /// Orders categories the way `game` does, `Greater` being the better
+    /// category, so the lowball games put a high card above a pair.
+    pub fn cmp_for(&self, other: &Self, game: GameVariant) -> Ordering {
+        let strength = |category: &Self| {
+            let category = match (game, category) {
+                (GameVariant::ShortDeck, RankCategory::Flush) => RankCategory::FullHouse,
+                (GameVariant::ShortDeck, RankCategory::FullHouse) => RankCategory::Flush,
+                (
+                    GameVariant::AceToFiveLowball,
+                    RankCategory::Straight | RankCategory::Flush | RankCategory::StraightFlush,
+                ) => RankCategory::HighCard,
+                (_, category) => *category,
+            };
+            match game {
+                GameVariant::TexasHoldem | GameVariant::ShortDeck | GameVariant::Omaha => {
+                    category as i8
+                }
+                GameVariant::AceToFiveLowball | GameVariant::Deuce7Lowball => -(category as i8),
+            }
+        };
+        strength(self).cmp(&strength(other))
+    }
+
+    /// A canonical example hand of this category, as space separated cards
+    /// that `Hand::new_from_strs` accepts.
+    pub fn example(&self) -> &'static str {
+        match self {
+            RankCategory::HighCard => "SA HK DQ CJ S9",
+            RankCategory::OnePair => "SA HA DK CQ SJ",
+            RankCategory::TwoPair => "SA HA DK CK SQ",
+            RankCategory::ThreeOfAKind => "SA HA DA CK SQ",
+            RankCategory::Straight => "ST H9 D8 C7 S6",
+            RankCategory::Flush => "SA SJ S8 S4 S2",
+            RankCategory::FullHouse => "SA HA DA CK SK",
+            RankCategory::FourOfAKind => "SA HA DA CA SK",
+            RankCategory::StraightFlush => "SA SK SQ SJ ST",
+        }
+    }
+}
+
+/// Number of distinct 5-card hand ranks.
+const DISTINCT_RANK_COUNT: usize = 7462;
+
+/// Every distinct 5-card hand rank, from the worst high card to the
+/// royal flush. Built once on first use, it is the ordering behind
+/// `equivalence_class`, `normalized_strength` and `rank_distance`.
+static DISTINCT_RANKS: Lazy<Vec<Rank>> = Lazy::new(|| {
+    let mut ranks = Vec::with_capacity(DISTINCT_RANK_COUNT);
+    for a in 0..13 {
+        for b in a..13 {
+            for c in b..13 {
+                for d in c..13 {
+                    for e in d..13 {
+                        // Five of a kind is not a valid hand.
+                        if a == e {
+                            continue;
+                        }
+                        let values = [a, b, c, d, e];
+                        ranks.push(rank_values(&values, false));
+                        if a < b && b < c && c < d && d < e {
+                            ranks.push(rank_values(&values, true));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    ranks.sort();
+    ranks.dedup();
+    ranks
+});
+
+/// Ranks five sorted card values, either all of one suit or spread
+/// over the suits so that they can't make a flush.
+fn rank_values(values: &[i8; 5], suited: bool) -> Rank {
+    let suits = Suit::suits();
+    let cards = values
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| {
+            let suit = if suited {
+                Suit::Spade
+            } else {
+                suits[i % 4].clone()
+            };
+            Card::new(suit, card::Rank::from_int(v + 2).unwrap())
+        })
+        .collect();
+    Hand::new_with_cards(cards).rank_five()
+}
+
+impl Rank {
+    pub fn category(&self) -> RankCategory {
+        match self {
+            Rank::HighCard(_) => RankCategory::HighCard,
+            Rank::OnePair(_) => RankCategory::OnePair,
+            Rank::TwoPair(_) => RankCategory::TwoPair,
+            Rank::ThreeOfAKind(_) => RankCategory::ThreeOfAKind,
+            Rank::Straight(_) => RankCategory::Straight,
+            Rank::Flush(_) => RankCategory::Flush,
+            Rank::FullHouse(_) => RankCategory::FullHouse,
+            Rank::FourOfAKind(_) => RankCategory::FourOfAKind,
+            Rank::StraightFlush(_) => RankCategory::StraightFlush,
+        }
+    }
+
+    /// Builds a rank from its category and strength payload.
+    pub fn from_parts(category: RankCategory, payload: u16) -> Self {
+        match category {
+            RankCategory::HighCard => Rank::HighCard(payload),
+            RankCategory::OnePair => Rank::OnePair(payload),
+            RankCategory::TwoPair => Rank::TwoPair(payload),
+            RankCategory::ThreeOfAKind => Rank::ThreeOfAKind(payload),
+            RankCategory::Straight => Rank::Straight(payload),
+            RankCategory::Flush => Rank::Flush(payload),
+            RankCategory::FullHouse => Rank::FullHouse(payload),
+            RankCategory::FourOfAKind => Rank::FourOfAKind(payload),
+            RankCategory::StraightFlush => Rank::StraightFlush(payload),
+        }
+    }
+
+    /// The strength payload within the category.
+    pub fn payload(&self) -> u16 {
+        match *self {
+            Rank::HighCard(p)
+            | Rank::OnePair(p)
+            | Rank::TwoPair(p)
+            | Rank::ThreeOfAKind(p)
+            | Rank::Straight(p)
+            | Rank::Flush(p)
+            | Rank::FullHouse(p)
+            | Rank::FourOfAKind(p)
+            | Rank::StraightFlush(p) => p,
+        }
+    }
+
+    /// A single number that orders ranks the same way `Ord` does: the
+    /// category above the payload.
+    pub fn score(&self) -> u32 {
+        (self.category() as u32) << 16 | self.payload() as u32
+    }
+
+    /// Whether this rank is strictly better than one from an earlier
+    /// street, e.g. a turn card making two pair from one pair.
+    pub fn improves_over(&self, previous: &Rank) -> bool {
+        self > previous
+    }
+
+    /// Compares against a rank held elsewhere as its category and payload,
+    /// the same as `cmp` on the rebuilt `Rank`.
+    pub fn cmp_payload(&self, other_category: RankCategory, other_payload: u16) -> Ordering {
+        (self.category(), self.payload()).cmp(&(other_category, other_payload))
+    }
+
+    /// Like `score`, but without the kickers, so hands that a results
+    /// table would show as one row (e.g. all "trip kings") share a key.
+    /// High cards and flushes are grouped by their highest card. Hands of
+    /// fewer than 5 cards group with the full hands they'd make, since
+    /// their made ranks sit in the same nibbles.
+    pub fn group_key(&self) -> u32 {
+        let payload = self.payload();
+        let made = match self {
+            Rank::HighCard(_) | Rank::Flush(_) => payload.checked_ilog2().map_or(0, |b| 1 << b),
+            Rank::OnePair(_) => payload & 0xF000,
+            Rank::TwoPair(_) => payload & 0x0FF0,
+            Rank::ThreeOfAKind(_) => payload & 0x0F00,
+            Rank::FourOfAKind(_) => payload & 0x00F0,
+            Rank::Straight(_) | Rank::FullHouse(_) | Rank::StraightFlush(_) => payload,
+        };
+        (self.category() as u32) << 16 | made as u32
+    }
+
+    /// Encodes the rank as 3 bytes: the category tag (`0` for high card up
+    /// to `8` for straight flush) followed by the payload in big-endian order.
+    pub fn to_bytes(&self) -> [u8; 3] {
+        let [high, low] = self.payload().to_be_bytes();
+        [self.category() as u8, high, low]
+    }
+
+    /// Decodes bytes written by `to_bytes`, returning `None` for an
+    /// unknown category tag.
+    pub fn from_bytes(bytes: [u8; 3]) -> Option<Self> {
+        let category = *RankCategory::all().get(bytes[0] as usize)?;
+        let payload = u16::from_be_bytes([bytes[1], bytes[2]]);
+        Some(Self::from_parts(category, payload))
+    }
+
+    /// Maps this rank onto `[0, 1]` by its position among all 7462
+    /// distinct 5-card hands, where the worst high card is `0.0` and
+    /// a royal flush is `1.0`.
+    pub fn normalized_strength(&self) -> f32 {
+        self.class_index() as f32 / (DISTINCT_RANK_COUNT - 1) as f32
+    }
+
+    /// Share of all 5 card hands this rank strictly beats, e.g. about 0.5
+    /// for a pair of twos. Each class counts as many times as there are
+    /// hands of it.
+    pub fn beats_fraction(&self) -> f64 {
+        let category = self.category();
+        let below: u32 = CATEGORY_FREQUENCIES[..category as usize].iter().sum();
+        let category_start = DISTINCT_RANKS.partition_point(|r| r.category() < category);
+        let within = (self.class_index() - category_start) as u32;
+        (below + within * CLASS_FREQUENCIES[category as usize]) as f64 / FIVE_CARD_HANDS as f64
+    }
+
+    /// The standard equivalence class number used by table based
+    /// evaluators, from `1` for a royal flush to `7462` for 7-5-4-3-2, so a
+    /// lower class is a better hand.
+    pub fn equivalence_class(&self) -> u16 {
+        (DISTINCT_RANK_COUNT - self.class_index()) as u16
+    }
+
+    /// Whether the two ranks are at most `classes` equivalence classes
+    /// apart, for grouping nearly equal hands.
+    pub fn close_to(&self, other: &Rank, classes: u16) -> bool {
+        self.equivalence_class().abs_diff(other.equivalence_class()) <= classes
+    }
+
+    /// Position among the distinct 5-card hands, `0` being the worst.
+    /// Payloads no real hand produces land on the nearest class.
+    fn class_index(&self) -> usize {
+        DISTINCT_RANKS
+            .binary_search(self)
+            .unwrap_or_else(|i| i.min(DISTINCT_RANK_COUNT - 1))
+    }
+
+    /// A human readable description, e.g. "Full House, Nines full of Aces".
+    pub fn describe(&self) -> String {
+        self.describe_with(&EnglishDescriber)
+    }
+
+    /// The description to show end users, which never includes the raw
+    /// payload bits. `Debug` keeps the packed form for developers.
+    pub fn to_user_string(&self) -> String {
+        self.describe()
+    }
+
+    /// Like `describe`, naming the suit of a flush or straight flush made
+    /// from `cards`, e.g. "Flush, Ace high of Spades". Other hands, or
+    /// cards that don't make this rank, get the plain description.
+    pub fn describe_with_cards(&self, cards: &[Card]) -> String {
+        let description = self.describe();
+        if !self.is_flush() || cards.rank() != *self {
+            return description;
+        }
+        match cards.best_five().first() {
+            Some(card) => format!("{} of {}s", description, card.suit().name()),
+            None => description,
+        }
+    }
+
+    /// Like `describe`, with the wording left to `describer`.
+    pub fn describe_with(&self, describer: &impl Describer) -> String {
+        let payload = self.payload();
+        let nibble = |i: u16| card_rank(payload >> (4 * i) & 0xF);
+        let high_bit = card_rank((USIZE_BIT - payload.leading_zeros() as u16).saturating_sub(1));
+        let ranks = match self {
+            Rank::HighCard(_) | Rank::Flush(_) => vec![high_bit],
+            Rank::OnePair(_) => vec![nibble(3)],
+            Rank::TwoPair(_) => vec![nibble(2), nibble(1)],
+            Rank::ThreeOfAKind(_) => vec![nibble(2)],
+            Rank::Straight(_) | Rank::StraightFlush(_) => vec![card_rank(payload + 3)],
+            Rank::FullHouse(_) => vec![nibble(1), nibble(0)],
+            Rank::FourOfAKind(_) => vec![nibble(1)],
+        };
+        describer.describe(self.category(), &ranks)
+    }
+
+    /// The nickname of a straight or straight flush: "Wheel" for A-5 and
+    /// "Broadway" for T-A. `None` for every other hand.
+    pub fn straight_name(&self) -> Option<&'static str> {
+        match self {
+            Rank::Straight(0) | Rank::StraightFlush(0) => Some("Wheel"),
+            Rank::Straight(9) | Rank::StraightFlush(9) => Some("Broadway"),
+            _ => None,
+        }
+    }
+
+    pub fn is_high_card(&self) -> bool {
+        matches!(self, Rank::HighCard(_))
+    }
+
+    pub fn is_one_pair(&self) -> bool {
+        matches!(self, Rank::OnePair(_))
+    }
+
+    pub fn is_two_pair(&self) -> bool {
+        matches!(self, Rank::TwoPair(_))
+    }
+
+    pub fn is_three_of_a_kind(&self) -> bool {
+        matches!(self, Rank::ThreeOfAKind(_))
+    }
+
+    /// True for both `Straight` and `StraightFlush`.
+    pub fn is_straight(&self) -> bool {
+        matches!(self, Rank::Straight(_) | Rank::StraightFlush(_))
+    }
+
+    /// True for both `Flush` and `StraightFlush`.
+    pub fn is_flush(&self) -> bool {
+        matches!(self, Rank::Flush(_) | Rank::StraightFlush(_))
+    }
+
+    pub fn is_full_house(&self) -> bool {
+        matches!(self, Rank::FullHouse(_))
+    }
+
+    pub fn is_four_of_a_kind(&self) -> bool {
+        matches!(self, Rank::FourOfAKind(_))
+    }
+
+    pub fn is_straight_flush(&self) -> bool {
+        matches!(self, Rank::StraightFlush(_))
+    }
+}
+
+/// Shows the user facing description, see `Rank::to_user_string`.
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_user_string())
+    }
+}
+
+/// The card rank at a rank index, falling back to a two for payloads no
+/// hand produces.
+fn card_rank(idx: u16) -> card::Rank {
+    card::Rank::from_int(idx as i8 + 2).unwrap_or(card::Rank::Two)
+}
+
+/// Words for `Rank::describe_with`. Every method defaults to English, so
+/// a localization only overrides what it needs.
+pub trait Describer {
+    /// A single card rank, e.g. "Ace".
+    fn rank_name(&self, rank: &card::Rank) -> String {
+        rank.name().to_string()
+    }
+
+    /// Several cards of a rank, e.g. "Sixes".
+    fn rank_plural(&self, rank: &card::Rank) -> String {
+        match rank {
+            card::Rank::Six => "Sixes".to_string(),
+            _ => format!("{}s", rank.name()),
+        }
+    }
+
+    /// Describes a hand of the category. `ranks` are the card ranks that
+    /// name the hand: the high card for high cards, flushes and straights,
+    /// the paired ranks highest first otherwise.
+    fn describe(&self, category: RankCategory, ranks: &[card::Rank]) -> String {
+        let name = |i: usize| self.rank_name(&ranks[i]);
+        let plural = |i: usize| self.rank_plural(&ranks[i]);
+        match category {
+            RankCategory::HighCard => format!("High Card, {}", name(0)),
+            RankCategory::OnePair => format!("Pair of {}", plural(0)),
+            RankCategory::TwoPair => format!("Two Pair, {} and {}", plural(0), plural(1)),
+            RankCategory::ThreeOfAKind => format!("Three of a Kind, {}", plural(0)),
+            RankCategory::Straight => format!("Straight, {} high", name(0)),
+            RankCategory::Flush => format!("Flush, {} high", name(0)),
+            RankCategory::FullHouse => format!("Full House, {} full of {}", plural(0), plural(1)),
+            RankCategory::FourOfAKind => format!("Four of a Kind, {}", plural(0)),
+            RankCategory::StraightFlush if ranks[0] == card::Rank::Ace => "Royal Flush".to_string(),
+            RankCategory::StraightFlush => format!("Straight Flush, {} high", name(0)),
+        }
+    }
+}
+
+/// The default English `Describer`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnglishDescriber;
+
+impl Describer for EnglishDescriber {}
+
 /// usize bits of poker values
 const USIZE_BIT: u16 = 16;
 
@@ -38,8 +530,62 @@ fn rank_straight(value_set: u16) -> Option<u16> {
     }
 }
 
+/// Bit mask for the short deck wheel (Ace, six, seven, eight, nine)
+const SHORT_DECK_WHEEL: u16 = 0b1_0000_1111_0000;
+
+fn rank_straight_short_deck(value_set: u16) -> Option<u16> {
+    rank_straight(value_set)
+        .or_else(|| (value_set & SHORT_DECK_WHEEL == SHORT_DECK_WHEEL).then_some(0))
+}
+
+/// A rank from `HandRanker::rank_wrapping`. Its straight payloads are
+/// shifted up by 3 to make room for the wrapping straights below the
+/// wheel, so it is kept apart from `Rank` and only orders against other
+/// wrapping ranks.
+#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Hash, Debug)]
+pub struct WrappingRank(Rank);
+
+impl WrappingRank {
+    pub fn category(&self) -> RankCategory {
+        self.0.category()
+    }
+
+    /// Whether this is a straight or straight flush that wraps around the
+    /// ace, such as Q-K-A-2-3.
+    pub fn is_wrapped(&self) -> bool {
+        self.0.is_straight() && self.0.payload() < WRAPS.len() as u16
+    }
+
+    /// The same hand as a standard `Rank`, or `None` for a wrapped
+    /// straight, which standard rules don't count.
+    pub fn to_standard(&self) -> Option<Rank> {
+        match self.0 {
+            _ if self.is_wrapped() => None,
+            Rank::Straight(p) => Some(Rank::Straight(p - WRAPS.len() as u16)),
+            Rank::StraightFlush(p) => Some(Rank::StraightFlush(p - WRAPS.len() as u16)),
+            ref rank => Some(rank.clone()),
+        }
+    }
+}
+
+/// Bit masks for the straights that wrap around the ace, lowest first
+/// (J-Q-K-A-2, Q-K-A-2-3, K-A-2-3-4).
+const WRAPS: [u16; 3] = [0b1_1110_0000_0001, 0b1_1100_0000_0011, 0b1_1000_0000_0111];
+
+fn rank_straight_wrapping(value_set: u16) -> Option<u16> {
+    if let Some(rank) = rank_straight(value_set) {
+        return Some(rank + WRAPS.len() as u16);
+    }
+    WRAPS
+        .iter()
+        .rposition(|&wrap| value_set & wrap == wrap)
+        .map(|idx| idx as u16)
+}
+
+/// Keeps only the highest set bit, or `0` when there is none, such as a
+/// missing kicker in a hand of fewer than 5 cards.
 fn keep_highest(rank: u16) -> u16 {
-    1 << (USIZE_BIT - rank.leading_zeros() as u16 - 1)
+    rank.checked_ilog2().map_or(0, |idx| 1 << idx)
 }
 
 fn keep_n(rank: u16, to_keep: u16) -> u16 {
@@ -50,69 +596,118 @@ fn keep_n(rank: u16, to_keep: u16) -> u16 {
     result
 }
 
+/// Packs the ranks set in `major` and then `minor`, highest first, as
+/// 4-bit rank indices, left aligned to the `width` nibbles of a full 5
+/// card hand. A hand of fewer cards keeps its made ranks in the same
+/// nibbles and has zeros for the missing kickers.
+fn pack(major: u16, minor: u16, width: u16) -> u16 {
+    let mut packed = 0;
+    let mut nibbles = 0;
+    for set in [major, minor] {
+        let mut bits = set;
+        while bits != 0 {
+            let idx = USIZE_BIT - bits.leading_zeros() as u16 - 1;
+            packed = packed << 4 | idx;
+            nibbles += 1;
+            bits ^= 1 << idx;
+        }
+    }
+    packed << (4 * width.saturating_sub(nibbles))
+}
+
 fn find_flush(suit_value_sets: &[u16]) -> Option<usize> {
     suit_value_sets.iter().position(|sv| sv.count_ones() >= 5)
 }
 
-pub trait HandRanker {
-    fn cards(&self) -> &[Card];
+/// Finds the best 5 card hand from the output of `compute_counts`, using
+/// `straight` to detect straights.
+fn rank_counts(counts: ([u16; 5], [u16; 4], u16), straight: fn(u16) -> Option<u16>) -> Rank {
+    let (count_to_value, suit_value_sets, value_set) = counts;
 
-    /// Rank the cards to find the best 5 card hand.
-    fn rank(&self) -> Rank {
-        let (count_to_value, suit_value_sets, value_set) = self.compute_counts();
-
-        if let Some(flush_idx) = find_flush(&suit_value_sets) {
-            if let Some(rank) = rank_straight(suit_value_sets[flush_idx]) {
-                return Rank::StraightFlush(rank);
-            } else {
-                let rank = keep_n(suit_value_sets[flush_idx], 5);
-                return Rank::Flush(rank);
-            }
+    if let Some(flush_idx) = find_flush(&suit_value_sets) {
+        if let Some(rank) = straight(suit_value_sets[flush_idx]) {
+            return Rank::StraightFlush(rank);
+        } else {
+            let rank = keep_n(suit_value_sets[flush_idx], 5);
+            return Rank::Flush(rank);
         }
+    }
 
-        if count_to_value[4] != 0 {
-            let high = keep_highest(value_set ^ count_to_value[4]);
-            return Rank::FourOfAKind(count_to_value[4] << 13 | high);
-        }
+    if count_to_value[4] != 0 {
+        let high = keep_highest(value_set ^ count_to_value[4]);
+        return Rank::FourOfAKind(pack(count_to_value[4], high, 2));
+    }
 
-        if count_to_value[3] != 0 && count_to_value[3].count_ones() == 2 {
-            let set = keep_highest(count_to_value[3]);
-            let pair = count_to_value[3] ^ set;
-            return Rank::FullHouse(set << 13 | pair);
-        }
+    if count_to_value[3] != 0 && count_to_value[3].count_ones() == 2 {
+        let set = keep_highest(count_to_value[3]);
+        let pair = count_to_value[3] ^ set;
+        return Rank::FullHouse(pack(set, pair, 2));
+    }
 
-        if count_to_value[3] != 0 && count_to_value[2] != 0 {
-            let set = count_to_value[3];
-            let pair = keep_highest(count_to_value[2]);
-            return Rank::FullHouse(set << 13 | pair);
-        }
+    if count_to_value[3] != 0 && count_to_value[2] != 0 {
+        let set = count_to_value[3];
+        let pair = keep_highest(count_to_value[2]);
+        return Rank::FullHouse(pack(set, pair, 2));
+    }
 
-        if let Some(s_rank) = rank_straight(value_set) {
-            return Rank::Straight(s_rank);
-        }
+    if let Some(s_rank) = straight(value_set) {
+        return Rank::Straight(s_rank);
+    }
 
-        if count_to_value[3] != 0 {
-            let low = keep_n(value_set ^ count_to_value[3], 2);
-            return Rank::ThreeOfAKind(count_to_value[3] << 13 | low);
-        }
+    if count_to_value[3] != 0 {
+        let low = keep_n(value_set ^ count_to_value[3], 2);
+        return Rank::ThreeOfAKind(pack(count_to_value[3], low, 3));
+    }
 
-        if count_to_value[2].count_ones() >= 2 {
-            let pairs = keep_n(count_to_value[2], 2);
-            let low = keep_highest(value_set ^ pairs);
-            return Rank::TwoPair(pairs << 13 | low);
-        }
+    if count_to_value[2].count_ones() >= 2 {
+        let pairs = keep_n(count_to_value[2], 2);
+        let low = keep_highest(value_set ^ pairs);
+        return Rank::TwoPair(pack(pairs, low, 3));
+    }
 
-        if count_to_value[2] == 0 {
-            return Rank::HighCard(keep_n(value_set, 5));
-        }
+    if count_to_value[2] == 0 {
+        return Rank::HighCard(keep_n(value_set, 5));
+    }
+
+    let pair = count_to_value[2];
+    let low = keep_n(value_set ^ count_to_value[2], 3);
+    Rank::OnePair(pack(pair, low, 4))
+}
 
-        let pair = count_to_value[2];
-        let low = keep_n(value_set ^ count_to_value[2], 3);
-        Rank::OnePair(pair << 13 | low)
+pub trait HandRanker {
+    fn cards(&self) -> &[Card];
+
+    /// Rank the cards to find the best 5 card hand.
+    fn rank(&self) -> Rank {
+        rank_counts(self.compute_counts(), rank_straight)
+    }
+
+    /// Like `rank`, for short deck (6+) where A-6-7-8-9 is the lowest
+    /// straight. It gets the wheel's payload of `0`.
+    fn rank_short_deck(&self) -> Rank {
+        rank_counts(self.compute_counts(), rank_straight_short_deck)
     }
 
-    /// Rank this hand assuming it has exactly 5 cards.
+    /// Like `rank`, but also counts "around the corner" straights such as
+    /// Q-K-A-2-3, which rank below the wheel. The result is its own type
+    /// since it only compares with other ranks from this method.
+    fn rank_wrapping(&self) -> WrappingRank {
+        WrappingRank(rank_counts(self.compute_counts(), rank_straight_wrapping))
+    }
+
+    /// Rank the cards purely on how often each rank appears, never making
+    /// straights or flushes. This is the shared base for lowball games.
+    fn rank_no_straight_flush(&self) -> Rank {
+        let (count_to_value, _, value_set) = self.compute_counts();
+        rank_counts((count_to_value, [0; 4], value_set), |_| None)
+    }
+
+    /// Rank this hand assuming it has exactly 5 cards. Any other number of
+    /// cards falls back to `rank`.
     fn rank_five(&self) -> Rank {
+        if self.cards().len() != 5 {
+            return self.rank();
+        }
         let (count_to_value, suit_value_sets, value_set) = self.compute_counts();
         let unique_card_count = value_set.count_ones();
 
@@ -129,31 +724,56 @@ pub trait HandRanker {
             4 => {
                 let major_rank = count_to_value[2];
                 let minor_rank = value_set ^ major_rank;
-                Rank::OnePair(major_rank << 13 | minor_rank)
+                Rank::OnePair(pack(major_rank, minor_rank, 4))
             }
             3 => {
                 if count_to_value[3] != 0 {
                     let major_rank = count_to_value[3];
                     let minor_rank = value_set ^ major_rank;
-                    Rank::ThreeOfAKind(major_rank << 13 | minor_rank)
+                    Rank::ThreeOfAKind(pack(major_rank, minor_rank, 3))
                 } else {
                     let major_rank = count_to_value[2];
                     let minor_rank = value_set ^ major_rank;
-                    Rank::TwoPair(major_rank << 13 | minor_rank)
+                    Rank::TwoPair(pack(major_rank, minor_rank, 3))
                 }
             }
             2 => {
                 if count_to_value[3] != 0 {
                     let major_rank = count_to_value[3];
                     let minor_rank = value_set ^ major_rank;
-                    Rank::FullHouse(major_rank << 13 | minor_rank)
+                    Rank::FullHouse(pack(major_rank, minor_rank, 2))
                 } else {
                     let major_rank = count_to_value[4];
                     let minor_rank = value_set ^ major_rank;
-                    Rank::FourOfAKind(major_rank << 13 | minor_rank)
+                    Rank::FourOfAKind(pack(major_rank, minor_rank, 2))
                 }
             }
-            _ => unreachable!(),
+            // Only reachable with duplicated cards.
+            _ => self.rank(),
+        }
+    }
+
+    /// Like `rank`, also saying whether every card plays in the best five,
+    /// which is only the case for hands of five cards or fewer.
+    fn rank_with_usage(&self) -> (Rank, bool) {
+        (self.rank(), self.cards().len() <= 5)
+    }
+
+    /// The five cards that make the best hand, in their original order.
+    /// Hands of five cards or fewer are returned whole.
+    fn best_five(&self) -> Vec<Card> {
+        match best_five_positions(self.cards()) {
+            Some(used) => pick(self.cards(), |i| used.contains(&i)),
+            None => self.cards().to_vec(),
+        }
+    }
+
+    /// The cards left out of `best_five`, e.g. the two cards that don't
+    /// play in a 7 card hand.
+    fn unused_cards(&self) -> Vec<Card> {
+        match best_five_positions(self.cards()) {
+            Some(used) => pick(self.cards(), |i| !used.contains(&i)),
+            None => Vec::new(),
         }
     }
 
@@ -182,6 +802,28 @@ pub trait HandRanker {
         }
 
         for (value, &count) in value_to_count.iter().enumerate() {
+            // Counts above 4 only come from duplicated cards.
+            count_to_value[count.min(4) as usize] |= 1 << value;
+        }
+
+        (count_to_value, suit_value_sets, value_set)
+    }
+
+    /// Same result as `compute_counts`, built from the `CARD_BITS` lookup
+    /// table with the per rank counts packed into one integer.
+    fn compute_counts_fast(&self) -> ([u16; 5], [u16; 4], u16) {
+        let mut suit_value_sets: [u16; 4] = [0; 4];
+        let mut packed_counts: u64 = 0; // 4 bits of count for each value
+        for c in self.cards() {
+            let (value_bit, suit, count_unit) = CARD_BITS[c.to_index() as usize];
+            suit_value_sets[suit] |= value_bit;
+            packed_counts += count_unit;
+        }
+
+        let value_set = suit_value_sets.iter().fold(0, |set, &sv| set | sv);
+        let mut count_to_value: [u16; 5] = [0; 5];
+        for value in 0..13 {
+            let count = (packed_counts >> (4 * value) & 0xF).min(4);
             count_to_value[count as usize] |= 1 << value;
         }
 
@@ -189,21 +831,143 @@ pub trait HandRanker {
     }
 }
 
-/// Implementation for `Hand`
-impl HandRanker for Hand {
+/// What each card adds in `compute_counts_fast`, indexed by
+/// `Card::to_index`: its value bit, its suit and a count of one in its
+/// value's nibble.
+const CARD_BITS: [(u16, usize, u64); 52] = {
+    let mut bits = [(0, 0, 0); 52];
+    let mut i = 0;
+    while i < 52 {
+        let value = i % 13;
+        bits[i] = (1 << value, i / 13, 1 << (4 * value));
+        i += 1;
+    }
+    bits
+};
+
+/// Positions of the cards in the best 5 card hand, or `None` for fewer
+/// than five cards. The first subset wins a tie.
+fn best_five_positions(cards: &[Card]) -> Option<[usize; 5]> {
+    let mut best: Option<(Rank, [usize; 5])> = None;
+    for picked in five_position_subsets(cards.len()) {
+        let rank = picked.map(|i| cards[i].clone()).rank_five();
+        if best.as_ref().is_none_or(|(r, _)| rank > *r) {
+            best = Some((rank, picked));
+        }
+    }
+    best.map(|(_, picked)| picked)
+}
+
+/// Every 5 card subset of `cards`, C(n, 5) of them, each in the original
+/// card order. Yields nothing for fewer than five cards.
+pub fn five_card_subsets(cards: &[Card]) -> impl Iterator<Item = [Card; 5]> + '_ {
+    five_position_subsets(cards.len()).map(move |picked| picked.map(|i| cards[i].clone()))
+}
+
+/// Every increasing choice of 5 positions out of `n`, in lexicographic
+/// order.
+fn five_position_subsets(n: usize) -> impl Iterator<Item = [usize; 5]> {
+    let first = (n >= 5).then_some([0, 1, 2, 3, 4]);
+    std::iter::successors(first, move |picked| {
+        // Step to the next combination of 5 card positions.
+        let i = (0..5).rfind(|&i| picked[i] < n - 5 + i)?;
+        let mut next = *picked;
+        next[i] += 1;
+        for j in i + 1..5 {
+            next[j] = next[j - 1] + 1;
+        }
+        Some(next)
+    })
+}
+
+fn pick(cards: &[Card], keep: impl Fn(usize) -> bool) -> Vec<Card> {
+    cards
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| keep(i))
+        .map(|(_, c)| c.clone())
+        .collect()
+}
+
+/// Implementation for anything that can be viewed as cards, such as
+/// `Hand`, `Vec<Card>`, arrays and slices.
+impl<T: AsRef<[Card]> + ?Sized> HandRanker for T {
     fn cards(&self) -> &[Card] {
-        &self.cards()
+        self.as_ref()
+    }
+}
+
+/// How many distinct 5-card hand classes `a` is above `b`; negative when
+/// `a` is the weaker hand and `1` for adjacent hands.
+pub fn rank_distance(a: &Rank, b: &Rank) -> i32 {
+    a.class_index() as i32 - b.class_index() as i32
+}
+
+/// Compares two ranks under short-deck (6+) rules, where a flush beats a
+/// full house. The derived `Ord` on `Rank` keeps the standard ordering.
+pub fn rank_cmp_short_deck(a: &Rank, b: &Rank) -> Ordering {
+    a.category()
+        .cmp_for(&b.category(), GameVariant::ShortDeck)
+        .then(a.payload().cmp(&b.payload()))
+}
+
+/// Finds the strongest hand, returning its index and rank. The first hand
+/// wins a tie.
+pub fn best_hand(hands: impl IntoIterator<Item = Hand>) -> Option<(usize, Rank)> {
+    extreme_hand(hands, std::cmp::Ordering::Greater)
+}
+
+/// Finds the weakest hand, returning its index and rank. The first hand
+/// wins a tie.
+pub fn worst_hand(hands: impl IntoIterator<Item = Hand>) -> Option<(usize, Rank)> {
+    extreme_hand(hands, std::cmp::Ordering::Less)
+}
+
+/// Index of the strongest of several raw card sets, the first one winning
+/// a tie like in `compare_ranks`.
+///
+/// # Panics
+///
+/// If `boards` is empty.
+pub fn best_hand_among(boards: &[Vec<Card>]) -> usize {
+    extreme_hand(boards, std::cmp::Ordering::Greater)
+        .expect("best_hand_among needs at least one card set")
+        .0
+}
+
+fn extreme_hand(
+    hands: impl IntoIterator<Item = impl HandRanker>,
+    wanted: std::cmp::Ordering,
+) -> Option<(usize, Rank)> {
+    let mut extreme: Option<(usize, Rank)> = None;
+    for (i, hand) in hands.into_iter().enumerate() {
+        let rank = hand.rank();
+        if extreme.as_ref().is_none_or(|(_, r)| rank.cmp(r) == wanted) {
+            extreme = Some((i, rank));
+        }
+    }
+    extreme
+}
+
+/// A random 5 card hand of the category, e.g. for synthetic training
+/// data. Hands are drawn from a full deck until one matches, so it takes
+/// `1 / category.probability()` draws on average: about 2 for a high card
+/// and about 65,000 for a straight flush.
+pub fn sample_hand_of_category(category: RankCategory, rng: &mut impl Rng) -> Hand {
+    let deck = Deck::standard().get_all_cards();
+    loop {
+        let cards: Vec<Card> = deck.choose_multiple(rng, 5).cloned().collect();
+        if cards.rank().category() == category {
+            return Hand::new_with_cards(cards);
+        }
     }
 }
-// // maybe don't need this
-// impl HandRanker for Vec<Card> {
-//     fn cards(&self) -> &[Card] {
-//         &self[..]
-//     }
-// }
 
 /// Compares the ranks of multiple players and returns the index of the winner(s).
 /// If there is a tie, returns the indices of all tied players.
+///
+/// The winners are always listed in ascending index order, so the same
+/// ranks give the same list.
 pub fn compare_ranks(ranks: &[Rank]) -> Vec<usize> {
     let mut winners: Vec<usize> = vec![];
     if ranks.is_empty() {
@@ -230,11 +994,95 @@ pub fn compare_ranks(ranks: &[Rank]) -> Vec<usize> {
     winners
 }
 
+/// Gives each player a 1-based finishing place. Tied players share a place
+/// and the places after them are skipped, so a tie for first goes 1, 1, 3.
+pub fn assign_places(ranks: &[Rank]) -> Vec<usize> {
+    let mut places = vec![0; ranks.len()];
+    let mut remaining: Vec<usize> = (0..ranks.len()).collect();
+    let mut placed = 0;
+    while !remaining.is_empty() {
+        let left: Vec<Rank> = remaining.iter().map(|&i| ranks[i].clone()).collect();
+        let winners = compare_ranks(&left);
+        for &w in &winners {
+            places[remaining[w]] = placed + 1;
+        }
+        placed += winners.len();
+        let mut w = 0;
+        remaining.retain(|_| {
+            w += 1;
+            !winners.contains(&(w - 1))
+        });
+    }
+    places
+}
+
+/// Serializes a `Rank` for people rather than programs, as
+/// `{"category":"FullHouse","description":"Full House, Nines full of Aces"}`.
+///
+/// The description leaves out kickers, so deserializing picks the strongest
+/// rank with that category and description. Only ranks without kickers,
+/// such as straights and full houses, come back exactly.
+#[cfg(feature = "serde")]
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ReadableRank(pub Rank);
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct ReadableRankRepr {
+    category: RankCategory,
+    description: String,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ReadableRank {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        ReadableRankRepr {
+            category: self.0.category(),
+            description: self.0.describe(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ReadableRank {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let repr = ReadableRankRepr::deserialize(deserializer)?;
+        DISTINCT_RANKS
+            .iter()
+            .rev()
+            .find(|r| r.category() == repr.category && r.describe() == repr.description)
+            .map(|r| ReadableRank(r.clone()))
+            .ok_or_else(|| {
+                serde::de::Error::custom(format!(
+                    "no {:?} is described as \"{}\"",
+                    repr.category, repr.description
+                ))
+            })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::poker::card;
+    use crate::poker::deck::Deck;
     use crate::poker::hand::Hand;
+    use std::collections::HashSet;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    /// Ranks a hand written as space separated cards, e.g. `"sa ha dk cq sj"`.
+    fn rank_of(s: &str) -> Rank {
+        let cards: Vec<&str> = s.split(' ').collect();
+        Hand::new_from_strs(&cards).unwrap().rank()
+    }
 
     #[test]
     fn test_keep_highest() {
@@ -291,7 +1139,7 @@ mod tests {
     #[test]
     fn test_full_house() {
         let hand = Hand::new_from_strs(&["da", "ca", "d9", "c9", "s9"]).unwrap();
-        let rank = (1 << (card::Rank::Nine as u16)) << 13 | 1 << (card::Rank::Ace as u16);
+        let rank = (card::Rank::Nine as u16) << 4 | card::Rank::Ace as u16;
         assert!(Rank::FullHouse(rank) == hand.rank_five());
     }
 
@@ -299,18 +1147,18 @@ mod tests {
     fn test_two_pair() {
         // Make a two pair hand.
         let hand = Hand::new_from_strs(&["da", "ca", "D9", "c9", "st"]).unwrap();
-        let rank = (1 << card::Rank::Ace as u16 | 1 << card::Rank::Nine as u16) << 13
-            | 1 << card::Rank::Ten as u16;
+        let rank =
+            (card::Rank::Ace as u16) << 8 | (card::Rank::Nine as u16) << 4 | card::Rank::Ten as u16;
         assert!(Rank::TwoPair(rank) == hand.rank_five());
     }
 
     #[test]
     fn test_one_pair() {
         let hand = Hand::new_from_strs(&["da", "ca", "d9", "c8", "st"]).unwrap();
-        let rank = (1 << card::Rank::Ace as u16) << 13
-            | 1 << card::Rank::Nine as u16
-            | 1 << card::Rank::Eight as u16
-            | 1 << card::Rank::Ten as u16;
+        let rank = (card::Rank::Ace as u16) << 12
+            | (card::Rank::Ten as u16) << 8
+            | (card::Rank::Nine as u16) << 4
+            | card::Rank::Eight as u16;
 
         assert!(Rank::OnePair(rank) == hand.rank_five());
     }
@@ -319,9 +1167,8 @@ mod tests {
     fn test_four_of_a_kind() {
         let hand = Hand::new_from_strs(&["da", "ca", "sa", "ha", "st"]).unwrap();
         assert!(
-            Rank::FourOfAKind(
-                (1 << (card::Rank::Ace as u16) << 13) | 1 << (card::Rank::Ten as u16)
-            ) == hand.rank_five()
+            Rank::FourOfAKind((card::Rank::Ace as u16) << 4 | card::Rank::Ten as u16)
+                == hand.rank_five()
         );
     }
 
@@ -340,9 +1187,8 @@ mod tests {
     #[test]
     fn test_three_of_a_kind() {
         let hand = Hand::new_from_strs(&["c2", "s2", "h2", "s5", "d6"]).unwrap();
-        let rank = (1 << (card::Rank::Two as u16)) << 13
-            | 1 << (card::Rank::Five as u16)
-            | 1 << (card::Rank::Six as u16);
+        let rank =
+            (card::Rank::Two as u16) << 8 | (card::Rank::Six as u16) << 4 | card::Rank::Five as u16;
         assert!(Rank::ThreeOfAKind(rank) == hand.rank_five());
     }
 
@@ -389,8 +1235,8 @@ mod tests {
     #[test]
     fn test_rank_seven_four_kind() {
         let h = Hand::new_from_strs(&["s2", "h2", "d2", "c2", "dk", "h9", "s4"]).unwrap();
-        let four_rank = (1 << card::Rank::Two as u16) << 13;
-        let low_rank = 1 << card::Rank::King as u16;
+        let four_rank = (card::Rank::Two as u16) << 4;
+        let low_rank = card::Rank::King as u16;
         assert_eq!(Rank::FourOfAKind(four_rank | low_rank), h.rank());
     }
 
@@ -398,8 +1244,8 @@ mod tests {
     fn test_rank_seven_four_plus_set() {
         // Four of a kind plus a set.
         let h = Hand::new_from_strs(&["s2", "h2", "d2", "c2", "d8", "s8", "c8"]).unwrap();
-        let four_rank = (1 << card::Rank::Two as u16) << 13;
-        let low_rank = 1 << card::Rank::Eight as u16;
+        let four_rank = (card::Rank::Two as u16) << 4;
+        let low_rank = card::Rank::Eight as u16;
         assert_eq!(Rank::FourOfAKind(four_rank | low_rank), h.rank());
     }
 
@@ -407,8 +1253,8 @@ mod tests {
     fn test_rank_seven_full_house_two_sets() {
         // We have two sets use the highest set.
         let h = Hand::new_from_strs(&["sa", "h2", "d2", "c2", "d8", "s8", "c8"]).unwrap();
-        let set_rank = (1 << card::Rank::Eight as u16) << 13;
-        let low_rank = 1 << card::Rank::Two as u16;
+        let set_rank = (card::Rank::Eight as u16) << 4;
+        let low_rank = card::Rank::Two as u16;
         assert_eq!(Rank::FullHouse(set_rank | low_rank), h.rank());
     }
 
@@ -416,24 +1262,24 @@ mod tests {
     fn test_rank_seven_full_house_two_pair() {
         // Test to make sure that we pick the best pair.
         let h = Hand::new_from_strs(&["h2", "d2", "c2", "d8", "s8", "dk", "sk"]).unwrap();
-        let set_rank = (1 << card::Rank::Two as u16) << 13;
-        let low_rank = 1 << card::Rank::King as u16;
+        let set_rank = (card::Rank::Two as u16) << 4;
+        let low_rank = card::Rank::King as u16;
         assert_eq!(Rank::FullHouse(set_rank | low_rank), h.rank());
     }
 
     #[test]
     fn test_two_pair_from_three_pair() {
         let h = Hand::new_from_strs(&["h2", "d2", "d8", "s8", "dk", "sk", "ht"]).unwrap();
-        let pair_rank = ((1 << card::Rank::King as u16) | (1 << card::Rank::Eight as u16)) << 13;
-        let low_rank = 1 << card::Rank::Ten as u16;
+        let pair_rank = (card::Rank::King as u16) << 8 | (card::Rank::Eight as u16) << 4;
+        let low_rank = card::Rank::Ten as u16;
         assert_eq!(Rank::TwoPair(pair_rank | low_rank), h.rank());
     }
 
     #[test]
     fn test_rank_seven_two_pair() {
         let h = Hand::new_from_strs(&["h2", "d2", "d8", "s8", "dk", "s6", "ht"]).unwrap();
-        let pair_rank = ((1 << card::Rank::Two as u16) | (1 << card::Rank::Eight as u16)) << 13;
-        let low_rank = 1 << card::Rank::King as u16;
+        let pair_rank = (card::Rank::Eight as u16) << 8 | (card::Rank::Two as u16) << 4;
+        let low_rank = card::Rank::King as u16;
         assert_eq!(Rank::TwoPair(pair_rank | low_rank), h.rank());
     }
 
@@ -459,6 +1305,844 @@ mod tests {
         assert_eq!(compare_ranks(&ranks), vec![0]);
 
         let ranks: Vec<Rank> = vec![];
-        assert_eq!(compare_ranks(&ranks), vec![]);
+        assert_eq!(compare_ranks(&ranks), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_assign_places() {
+        let ranks = [
+            hand!["sa", "ha", "dk", "c7", "s2"].rank(),
+            hand!["sk", "hk", "d7", "c7", "s2"].rank(),
+            hand!["dk", "ck", "h7", "s7", "d2"].rank(),
+        ];
+        assert_eq!(assign_places(&ranks), vec![3, 1, 1]);
+
+        let ranks = [
+            hand!["sa", "hk", "d9", "c7", "s2"].rank(),
+            hand!["ha", "hk", "h9", "h7", "h2"].rank(),
+            hand!["da", "ck", "h9", "s7", "d2"].rank(),
+            hand!["s2", "h3", "d4", "c5", "s6"].rank(),
+        ];
+        assert_eq!(assign_places(&ranks), vec![3, 1, 3, 2]);
+        assert_eq!(assign_places(&[]), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_compute_counts_fast() {
+        let mut rng = StdRng::seed_from_u64(467);
+        for size in 0..=7 {
+            for _ in 0..500 {
+                let mut deck = Deck::standard();
+                let cards: Vec<Card> = (0..size)
+                    .map(|_| deck.draw_random(&mut rng).unwrap())
+                    .collect();
+                assert_eq!(cards.compute_counts(), cards.compute_counts_fast());
+            }
+        }
+        let all = Deck::standard().get_all_cards();
+        assert_eq!(all.compute_counts(), all.compute_counts_fast());
+    }
+
+    #[test]
+    fn test_any_card_count() {
+        let mut rng = StdRng::seed_from_u64(433);
+        for size in 0..=7 {
+            for _ in 0..300 {
+                let mut deck = Deck::standard();
+                let cards: Vec<Card> = (0..size)
+                    .map(|_| deck.draw_random(&mut rng).unwrap())
+                    .collect();
+                let rank = cards.rank();
+                assert_eq!(rank, cards.rank_five());
+                cards.rank_wrapping();
+                cards.rank_no_straight_flush();
+
+                if size < 5 {
+                    assert!(rank.category() != RankCategory::Straight);
+                    assert!(rank.category() != RankCategory::Flush);
+                    continue;
+                }
+                // The best 5 card subset must match the full ranking.
+                let mut best = None;
+                for skip in 0..1 << size {
+                    if (skip as u32).count_ones() != size as u32 - 5 {
+                        continue;
+                    }
+                    let five: Vec<Card> = (0..size)
+                        .filter(|i| skip & 1 << i == 0)
+                        .map(|i| cards[i].clone())
+                        .collect();
+                    best = best.max(Some(five.rank_five()));
+                }
+                assert_eq!(Some(rank), best);
+            }
+        }
+        let quads = Hand::new_from_strs(&["da", "ca", "ha", "sa"]).unwrap();
+        assert_eq!(RankCategory::FourOfAKind, quads.rank().category());
+        assert_eq!(Rank::HighCard(0), Vec::<Card>::new().rank());
+    }
+
+    #[test]
+    fn test_pair_ordering() {
+        let aces = Hand::new_from_strs(&["da", "ca", "d4", "c3", "s2"]).unwrap();
+        let kings = Hand::new_from_strs(&["dk", "ck", "dq", "cj", "s9"]).unwrap();
+        assert!(aces.rank_five() > kings.rank_five());
+    }
+
+    #[test]
+    fn test_distinct_ranks() {
+        assert_eq!(DISTINCT_RANK_COUNT, DISTINCT_RANKS.len());
+        assert_eq!(Rank::HighCard(0b10_1111), DISTINCT_RANKS[0]);
+        assert_eq!(
+            Rank::StraightFlush(9),
+            DISTINCT_RANKS[DISTINCT_RANK_COUNT - 1]
+        );
+        assert!(DISTINCT_RANKS.windows(2).all(|pair| pair[0] < pair[1]));
+        let distinct: HashSet<&Rank> = DISTINCT_RANKS.iter().collect();
+        assert_eq!(DISTINCT_RANK_COUNT, distinct.len());
+    }
+
+    #[test]
+    fn test_equivalence_class() {
+        let class = |s: &str| rank_of(s).equivalence_class();
+        assert_eq!(1, class("st sj sq sk sa"));
+        assert_eq!(10, class("sa s2 s3 s4 s5"));
+        assert_eq!(11, class("sa ha da ca sk"));
+        assert_eq!(7462, class("s7 h5 d4 c3 s2"));
+        // The best pair and the worst two pair.
+        assert_eq!(3326, class("sa ha dk cq sj"));
+        assert_eq!(3325, class("s3 h3 d2 c2 s4"));
+
+        for pair in DISTINCT_RANKS.windows(2) {
+            assert_eq!(pair[0].equivalence_class(), pair[1].equivalence_class() + 1);
+        }
+    }
+
+    #[test]
+    fn test_five_card_subsets() {
+        let seven = Hand::new_from_strs(&["sa", "ha", "dk", "cq", "sj", "h9", "d2"]).unwrap();
+        let six = &seven.cards()[..6];
+        for (cards, expected) in [(seven.cards(), 21), (six, 6)] {
+            let subsets: Vec<[Card; 5]> = five_card_subsets(cards).collect();
+            assert_eq!(expected, subsets.len());
+            for subset in &subsets {
+                let mut sorted = subset.to_vec();
+                sorted.sort();
+                sorted.dedup();
+                assert_eq!(5, sorted.len());
+                assert!(subset.iter().all(|c| cards.contains(c)));
+            }
+            let mut distinct = subsets.clone();
+            distinct.sort();
+            distinct.dedup();
+            assert_eq!(expected, distinct.len());
+        }
+        assert_eq!(1, five_card_subsets(&seven.cards()[..5]).count());
+        let deck = crate::poker::deck::Deck::standard().get_all_cards();
+        assert_eq!(2_598_960, five_card_subsets(&deck).count());
+        assert_eq!(0, five_card_subsets(&seven.cards()[..4]).count());
+    }
+
+    #[test]
+    fn test_describe_with_cards() {
+        let flush = Hand::new_from_strs(&["sa", "s9", "s7", "s4", "s2", "hk", "dk"]).unwrap();
+        let rank = flush.rank();
+        assert_eq!(
+            "Flush, Ace high of Spades",
+            rank.describe_with_cards(flush.cards())
+        );
+
+        let royal = Hand::new_from_strs(&["ht", "hj", "hq", "hk", "ha", "s2", "d3"]).unwrap();
+        assert_eq!(
+            "Royal Flush of Hearts",
+            royal.rank().describe_with_cards(royal.cards())
+        );
+
+        let pair = Hand::new_from_strs(&["sa", "ha", "s7", "s4", "s2"]).unwrap();
+        assert_eq!(
+            "Pair of Aces",
+            pair.rank().describe_with_cards(pair.cards())
+        );
+        assert_eq!("Flush, Ace high", rank.describe_with_cards(pair.cards()));
+    }
+
+    #[test]
+    fn test_best_hand_among() {
+        let cards = |s: &[&str]| Hand::new_from_strs(s).unwrap().cards().to_vec();
+        let boards = [
+            cards(&["sa", "ha", "dk", "cq", "sj"]),
+            cards(&["s9", "h9", "d9", "c2", "s3"]),
+            cards(&["sk", "hk", "dq", "c2", "s3"]),
+        ];
+        assert_eq!(1, best_hand_among(&boards));
+
+        let tied = [
+            cards(&["s2", "h5", "d9", "cj", "sk"]),
+            cards(&["sa", "ha", "dk", "cq", "sj"]),
+            cards(&["da", "ca", "hk", "sq", "hj"]),
+        ];
+        let ranks: Vec<Rank> = tied.iter().map(|c| c.rank()).collect();
+        assert_eq!(compare_ranks(&ranks)[0], best_hand_among(&tied));
+        assert_eq!(1, best_hand_among(&tied));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_best_hand_among_empty() {
+        best_hand_among(&[]);
+    }
+
+    #[test]
+    fn test_to_user_string() {
+        for rank in DISTINCT_RANKS.iter() {
+            let user = rank.to_user_string();
+            assert!(!user.chars().any(|c| c.is_ascii_digit()), "{}", user);
+            assert!(!user.contains(&rank.payload().to_string()));
+            assert_eq!(user, rank.to_string());
+        }
+        let rank = Rank::FullHouse(0x70);
+        assert_eq!("Full House, Nines full of Twos", format!("{}", rank));
+        assert_eq!("FullHouse(112)", format!("{:?}", rank));
+    }
+
+    #[test]
+    fn test_beats_fraction() {
+        let royal = rank_of("st sj sq sk sa").beats_fraction();
+        assert!(royal < 1.0 && royal > 0.99999);
+        assert_eq!(0.0, rank_of("s7 h5 d4 c3 s2").beats_fraction());
+
+        let worst_pair = rank_of("s2 h2 d3 c4 s5").beats_fraction();
+        assert!((worst_pair - 0.501).abs() < 0.001);
+
+        // Every category's classes add up to its frequency.
+        for (category, frequency) in RankCategory::all().iter().zip(CATEGORY_FREQUENCIES) {
+            let classes = DISTINCT_RANKS
+                .iter()
+                .filter(|r| r.category() == *category)
+                .count() as u32;
+            assert_eq!(frequency, classes * CLASS_FREQUENCIES[*category as usize]);
+        }
+        for pair in DISTINCT_RANKS.windows(2) {
+            assert!(pair[0].beats_fraction() < pair[1].beats_fraction());
+        }
+    }
+
+    #[test]
+    fn test_sample_hand_of_category() {
+        let mut rng = StdRng::seed_from_u64(487);
+        for category in RankCategory::all() {
+            let hand = sample_hand_of_category(category, &mut rng);
+            assert_eq!(5, hand.len());
+            assert_eq!(category, hand.rank().category());
+        }
+    }
+
+    #[test]
+    fn test_compare_ranks_sorted_winners() {
+        let mut rng = StdRng::seed_from_u64(491);
+        for _ in 0..500 {
+            // Draw from a few classes so that ties are common.
+            let players = rng.gen_range(1..10);
+            let ranks: Vec<Rank> = (0..players)
+                .map(|_| DISTINCT_RANKS[rng.gen_range(7455..7462)].clone())
+                .collect();
+            let winners = compare_ranks(&ranks);
+
+            let best = ranks.iter().max().unwrap();
+            let expected: Vec<usize> = (0..players).filter(|&i| ranks[i] == *best).collect();
+            assert!(winners.windows(2).all(|w| w[0] < w[1]), "{:?}", winners);
+            assert_eq!(expected, winners);
+        }
+    }
+
+    #[test]
+    fn test_straight_name() {
+        assert_eq!(Some("Wheel"), rank_of("sa h2 d3 c4 s5").straight_name());
+        assert_eq!(Some("Broadway"), rank_of("st hj dq ck sa").straight_name());
+        assert_eq!(Some("Wheel"), rank_of("sa s2 s3 s4 s5").straight_name());
+        assert_eq!(Some("Broadway"), rank_of("st sj sq sk sa").straight_name());
+        assert_eq!(None, rank_of("s2 h3 d4 c5 s6").straight_name());
+        assert_eq!(None, rank_of("sa ha dk cq sj").straight_name());
+        assert_eq!(None, Rank::Flush(0b1_1110_0000_0000).straight_name());
+    }
+
+    #[test]
+    fn test_close_to() {
+        let aces = rank_of("sa ha dk cq sj");
+        let next = rank_of("sa ha dk cq st");
+        assert!(aces.close_to(&next, 1));
+        assert!(!aces.close_to(&next, 0));
+        assert!(aces.close_to(&aces, 0));
+
+        let royal = rank_of("st sj sq sk sa");
+        assert!(!royal.close_to(&aces, 1000));
+        assert!(royal.close_to(&aces, 3325));
+    }
+
+    #[test]
+    fn test_normalized_strength() {
+        let royal = Hand::new_from_strs(&["sa", "sk", "sq", "sj", "st"]).unwrap();
+        assert!((royal.rank_five().normalized_strength() - 1.0).abs() < f32::EPSILON);
+
+        let worst = Hand::new_from_strs(&["s7", "h5", "d4", "c3", "s2"]).unwrap();
+        assert!(worst.rank_five().normalized_strength().abs() < f32::EPSILON);
+
+        let hands = [
+            ["s7", "h5", "d4", "c3", "s2"],
+            ["sa", "hk", "dq", "cj", "s9"],
+            ["s2", "h2", "d4", "c3", "s5"],
+            ["sa", "ha", "dk", "cq", "sj"],
+            ["s2", "h2", "d3", "c3", "s4"],
+            ["s2", "h2", "d2", "c3", "s4"],
+            ["sa", "h2", "d3", "c4", "s5"],
+            ["s2", "s4", "s5", "s6", "s8"],
+            ["s2", "h2", "d2", "c3", "s3"],
+            ["s2", "h2", "d2", "c2", "s3"],
+            ["sa", "s2", "s3", "s4", "s5"],
+        ];
+        let strengths: Vec<f32> = hands
+            .iter()
+            .map(|h| {
+                Hand::new_from_strs(h)
+                    .unwrap()
+                    .rank_five()
+                    .normalized_strength()
+            })
+            .collect();
+        assert!(strengths.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_predicates() {
+        assert!(Rank::HighCard(0).is_high_card());
+        assert!(Rank::OnePair(0).is_one_pair());
+        assert!(!Rank::OnePair(0).is_two_pair());
+        assert!(Rank::TwoPair(0).is_two_pair());
+        assert!(Rank::ThreeOfAKind(0).is_three_of_a_kind());
+        assert!(Rank::FullHouse(0).is_full_house());
+        assert!(!Rank::FullHouse(0).is_three_of_a_kind());
+        assert!(Rank::FourOfAKind(0).is_four_of_a_kind());
+
+        assert!(Rank::Straight(0).is_straight());
+        assert!(!Rank::Straight(0).is_flush());
+        assert!(Rank::Flush(0).is_flush());
+        assert!(!Rank::Flush(0).is_straight());
+        assert!(Rank::StraightFlush(0).is_straight());
+        assert!(Rank::StraightFlush(0).is_flush());
+        assert!(Rank::StraightFlush(0).is_straight_flush());
+        assert!(!Rank::Straight(0).is_straight_flush());
+    }
+
+    #[test]
+    fn test_category_examples() {
+        let examples: std::collections::HashSet<&str> =
+            RankCategory::all().iter().map(|c| c.example()).collect();
+        assert_eq!(9, examples.len());
+
+        for category in RankCategory::all() {
+            let cards: Vec<&str> = category.example().split_whitespace().collect();
+            let hand = Hand::new_from_strs(&cards).unwrap();
+            assert_eq!(category, hand.rank_five().category());
+        }
+    }
+
+    #[test]
+    fn test_category_probability() {
+        let total: f64 = RankCategory::all().iter().map(|c| c.probability()).sum();
+        assert!((total - 1.0).abs() < 1e-12);
+        assert!((RankCategory::Flush.probability() - 0.00197).abs() < 1e-5);
+        assert!((RankCategory::OnePair.probability() - 0.4226).abs() < 1e-4);
+        for pair in RankCategory::all()[1..].windows(2) {
+            assert!(pair[0].probability() > pair[1].probability());
+        }
+    }
+
+    #[test]
+    fn test_into_min_rank() {
+        let mins: Vec<Rank> = RankCategory::all()
+            .iter()
+            .map(|c| c.into_min_rank())
+            .collect();
+        let mut sorted = mins.clone();
+        sorted.sort();
+        assert_eq!(sorted, mins);
+        for (category, rank) in RankCategory::all().iter().zip(&mins) {
+            assert_eq!(*category, rank.category());
+        }
+
+        let two_pair = Hand::new_from_strs(&["s3", "h3", "d2", "c2", "s4"])
+            .unwrap()
+            .rank();
+        assert!(two_pair >= RankCategory::TwoPair.into_min_rank());
+        assert!(two_pair < RankCategory::ThreeOfAKind.into_min_rank());
+    }
+
+    #[test]
+    fn test_short_codes() {
+        let codes: Vec<&str> = RankCategory::all().iter().map(|c| c.short_code()).collect();
+        assert_eq!(
+            vec!["HC", "1P", "2P", "3K", "ST", "FL", "FH", "4K", "SF"],
+            codes
+        );
+    }
+
+    #[test]
+    fn test_category_for_game() {
+        use RankCategory::*;
+        assert_eq!(
+            Ordering::Less,
+            OnePair.cmp_for(&HighCard, GameVariant::AceToFiveLowball)
+        );
+        assert_eq!(
+            Ordering::Greater,
+            OnePair.cmp_for(&HighCard, GameVariant::TexasHoldem)
+        );
+        assert_eq!(
+            Ordering::Equal,
+            Straight.cmp_for(&HighCard, GameVariant::AceToFiveLowball)
+        );
+        assert_eq!(
+            Ordering::Less,
+            Straight.cmp_for(&HighCard, GameVariant::Deuce7Lowball)
+        );
+        assert_eq!(
+            Ordering::Greater,
+            Flush.cmp_for(&FullHouse, GameVariant::ShortDeck)
+        );
+
+        assert_eq!(
+            "One Pair",
+            OnePair.display_for(GameVariant::AceToFiveLowball)
+        );
+        assert_eq!(
+            "High Card",
+            Flush.display_for(GameVariant::AceToFiveLowball)
+        );
+        assert_eq!("Flush", Flush.display_for(GameVariant::Deuce7Lowball));
+    }
+
+    #[test]
+    fn test_category_dominates_payload() {
+        for pair in RankCategory::all().windows(2) {
+            let lower = Rank::from_parts(pair[0], u16::MAX);
+            let higher = Rank::from_parts(pair[1], 0);
+            assert!(lower < higher, "{:?} should lose to {:?}", lower, higher);
+            assert_ne!(lower, higher);
+        }
+    }
+
+    #[test]
+    fn test_category_boundaries() {
+        // The strongest real hand of each category against the weakest
+        // real hand of the next one.
+        for pair in RankCategory::all().windows(2) {
+            let max_lower = DISTINCT_RANKS
+                .iter()
+                .filter(|r| r.category() == pair[0])
+                .max()
+                .unwrap();
+            let min_higher = DISTINCT_RANKS
+                .iter()
+                .filter(|r| r.category() == pair[1])
+                .min()
+                .unwrap();
+            assert!(max_lower < min_higher);
+        }
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        for category in RankCategory::all() {
+            let cards: Vec<&str> = category.example().split_whitespace().collect();
+            let rank = Hand::new_from_strs(&cards).unwrap().rank_five();
+            let bytes = rank.to_bytes();
+            assert_eq!(category as u8, bytes[0]);
+            assert_eq!(Some(rank), Rank::from_bytes(bytes));
+        }
+
+        assert_eq!([5, 0x12, 0x34], Rank::Flush(0x1234).to_bytes());
+        assert_eq!(None, Rank::from_bytes([9, 0, 0]));
+    }
+
+    #[test]
+    fn test_wrap_straights() {
+        let h = Hand::new_from_strs(&["sq", "hk", "da", "c2", "s3"]).unwrap();
+        assert!(h.rank().is_high_card());
+        let rank = h.rank_wrapping();
+        assert_eq!(RankCategory::Straight, rank.category());
+        assert!(rank.is_wrapped());
+        assert_eq!(None, rank.to_standard());
+
+        let h = Hand::new_from_strs(&["hj", "hq", "hk", "ha", "h2", "d9"]).unwrap();
+        assert!(!h.rank().is_straight());
+        assert_eq!(RankCategory::StraightFlush, h.rank_wrapping().category());
+        assert!(h.rank_wrapping().is_wrapped());
+
+        // Wrapping straights rank below the wheel.
+        let wheel = Hand::new_from_strs(&["sa", "h2", "d3", "c4", "s5"]).unwrap();
+        let wrap = Hand::new_from_strs(&["sk", "ha", "d2", "c3", "s4"]).unwrap();
+        assert!(!wheel.rank_wrapping().is_wrapped());
+        assert!(wrap.rank_wrapping() < wheel.rank_wrapping());
+
+        // Hands without a wrapped straight match the standard ranking.
+        let broadway = Hand::new_from_strs(&["st", "hj", "dq", "ck", "sa"]).unwrap();
+        for hand in [&wheel, &broadway] {
+            assert_eq!(Some(hand.rank()), hand.rank_wrapping().to_standard());
+        }
+        assert_eq!(
+            Some("Broadway"),
+            broadway
+                .rank_wrapping()
+                .to_standard()
+                .unwrap()
+                .straight_name()
+        );
+        let pair = Hand::new_from_strs(&["sa", "ha", "d2", "c7", "s9"]).unwrap();
+        assert_eq!(Some(pair.rank()), pair.rank_wrapping().to_standard());
+    }
+
+    #[test]
+    fn test_rank_card_collections() {
+        let hand = Hand::new_from_strs(&["da", "dk", "dq", "dj", "dt", "d9", "d8"]).unwrap();
+        let vec: Vec<Card> = hand.cards().to_vec();
+        let array: [Card; 7] = vec.clone().try_into().unwrap();
+        let slice: &[Card] = &vec;
+
+        assert_eq!(Rank::StraightFlush(9), hand.rank());
+        assert_eq!(Rank::StraightFlush(9), vec.rank());
+        assert_eq!(Rank::StraightFlush(9), array.rank());
+        assert_eq!(Rank::StraightFlush(9), slice.rank());
+    }
+
+    #[test]
+    fn test_rank_distance() {
+        let royal = Hand::new_from_strs(&["sa", "sk", "sq", "sj", "st"]).unwrap();
+        let worst = Hand::new_from_strs(&["s7", "h5", "d4", "c3", "s2"]).unwrap();
+        assert_eq!(7461, rank_distance(&royal.rank(), &worst.rank()));
+        assert_eq!(-7461, rank_distance(&worst.rank(), &royal.rank()));
+        assert_eq!(0, rank_distance(&royal.rank(), &royal.rank()));
+
+        // 7-6-4-3-2 is the next high card up from 7-5-4-3-2.
+        let next = Hand::new_from_strs(&["s7", "h6", "d4", "c3", "s2"]).unwrap();
+        assert_eq!(1, rank_distance(&next.rank(), &worst.rank()));
+    }
+
+    #[test]
+    fn test_short_deck_cmp() {
+        let flush = Hand::new_from_strs(&["h6", "h8", "h9", "hj", "hk"])
+            .unwrap()
+            .rank();
+        let full_house = Hand::new_from_strs(&["sa", "ha", "da", "ck", "sk"])
+            .unwrap()
+            .rank();
+
+        assert!(flush < full_house);
+        assert_eq!(
+            std::cmp::Ordering::Greater,
+            rank_cmp_short_deck(&flush, &full_house)
+        );
+        assert_eq!(
+            std::cmp::Ordering::Less,
+            rank_cmp_short_deck(&full_house, &flush)
+        );
+
+        let quads = Rank::FourOfAKind(0);
+        assert_eq!(
+            std::cmp::Ordering::Less,
+            rank_cmp_short_deck(&flush, &quads)
+        );
+        assert_eq!(
+            std::cmp::Ordering::Equal,
+            rank_cmp_short_deck(&flush, &flush)
+        );
+    }
+
+    #[test]
+    fn test_describe() {
+        let cases = [
+            (["sa", "hk", "d9", "c7", "s2"], "High Card, Ace"),
+            (["s6", "h6", "d9", "c7", "s2"], "Pair of Sixes"),
+            (["sa", "ha", "d9", "c9", "s2"], "Two Pair, Aces and Nines"),
+            (["s2", "h2", "d2", "c9", "sk"], "Three of a Kind, Twos"),
+            (["sa", "h2", "d3", "c4", "s5"], "Straight, Five high"),
+            (["st", "hj", "dq", "ck", "sa"], "Straight, Ace high"),
+            (["hk", "h2", "h9", "h7", "h3"], "Flush, King high"),
+            (
+                ["s9", "h9", "d9", "ca", "sa"],
+                "Full House, Nines full of Aces",
+            ),
+            (["sq", "hq", "dq", "cq", "s3"], "Four of a Kind, Queens"),
+            (["h5", "h6", "h7", "h8", "h9"], "Straight Flush, Nine high"),
+            (["st", "sj", "sq", "sk", "sa"], "Royal Flush"),
+        ];
+        for (cards, expected) in cases {
+            let hand = Hand::new_from_strs(&cards).unwrap();
+            assert_eq!(expected, hand.rank().describe());
+        }
+    }
+
+    #[test]
+    fn test_describe_short_hands() {
+        let cases: [(&[&str], &str); 7] = [
+            (&["sa", "ha"], "Pair of Aces"),
+            (&["s2", "h2"], "Pair of Twos"),
+            (&["sa", "ha", "d3"], "Pair of Aces"),
+            (&["sa", "ha", "da"], "Three of a Kind, Aces"),
+            (&["sk", "hk", "dk", "ck"], "Four of a Kind, Kings"),
+            (&["sa", "ha", "d9", "c9"], "Two Pair, Aces and Nines"),
+            (&["sa"], "High Card, Ace"),
+        ];
+        for (cards, expected) in cases {
+            let hand = Hand::new_from_strs(cards).unwrap();
+            assert_eq!(expected, hand.rank().describe());
+        }
+
+        // Short hands still order against full ones.
+        let aces = Hand::new_from_strs(&["sa", "ha"]).unwrap().rank();
+        let kings = Hand::new_from_strs(&["sk", "hk", "dq", "cj", "s9"]).unwrap();
+        assert!(aces > kings.rank());
+    }
+
+    #[test]
+    fn test_describe_with_localizer() {
+        struct German;
+        impl Describer for German {
+            fn rank_name(&self, rank: &card::Rank) -> String {
+                match rank {
+                    card::Rank::Nine => "Neun".to_string(),
+                    card::Rank::Ace => "Ass".to_string(),
+                    _ => rank.name().to_string(),
+                }
+            }
+
+            fn rank_plural(&self, rank: &card::Rank) -> String {
+                match rank {
+                    card::Rank::Nine => "Neunen".to_string(),
+                    card::Rank::Ace => "Assen".to_string(),
+                    _ => self.rank_name(rank),
+                }
+            }
+
+            fn describe(&self, category: RankCategory, ranks: &[card::Rank]) -> String {
+                match category {
+                    RankCategory::FullHouse => format!(
+                        "Full House, {} und {}",
+                        self.rank_plural(&ranks[0]),
+                        self.rank_plural(&ranks[1])
+                    ),
+                    _ => EnglishDescriber.describe(category, ranks),
+                }
+            }
+        }
+
+        let boat = Hand::new_from_strs(&["s9", "h9", "d9", "ca", "sa"]).unwrap();
+        assert_eq!(
+            "Full House, Neunen und Assen",
+            boat.rank().describe_with(&German)
+        );
+        assert_eq!(
+            boat.rank().describe(),
+            boat.rank().describe_with(&EnglishDescriber)
+        );
+    }
+
+    #[test]
+    fn test_rank_with_usage() {
+        let five = Hand::new_from_strs(&["s2", "ha", "d9", "c3", "sa"]).unwrap();
+        assert_eq!((five.rank(), true), five.rank_with_usage());
+
+        let seven = Hand::new_from_strs(&["s2", "ha", "d9", "c3", "sa", "h9", "dk"]).unwrap();
+        let (rank, all_used) = seven.rank_with_usage();
+        assert_eq!(seven.rank(), rank);
+        assert!(!all_used);
+        assert_eq!(!all_used, !seven.unused_cards().is_empty());
+    }
+
+    #[test]
+    fn test_best_five_and_unused_cards() {
+        let hand = Hand::new_from_strs(&["s2", "ha", "d9", "c3", "sa", "h9", "dk"]).unwrap();
+        let best = hand.best_five();
+        let unused = hand.unused_cards();
+        assert_eq!(hand.rank(), best.rank_five());
+        assert_eq!(Hand::new_from_strs(&["s2", "c3"]).unwrap().cards(), unused);
+
+        let mut all = [best, unused].concat();
+        let mut cards = hand.cards().to_vec();
+        all.sort();
+        cards.sort();
+        assert_eq!(cards, all);
+
+        let short = Hand::new_from_strs(&["s2", "ha", "d9"]).unwrap();
+        assert_eq!(short.cards(), short.best_five());
+        assert!(short.unused_cards().is_empty());
+
+        // More cards than fit in a 32 bit mask.
+        let many = &Deck::standard().get_all_cards()[..33];
+        let best = many.best_five();
+        assert_eq!(many.rank(), best.rank_five());
+        assert_eq!(28, many.unused_cards().len());
+    }
+
+    #[test]
+    fn test_improves_over() {
+        let flop = Hand::new_from_strs(&["sa", "ha", "d9", "c7", "s4"]).unwrap();
+        let improved = flop
+            .with_added(&[Card::try_from_str("h9").unwrap()])
+            .unwrap();
+        assert!(improved.rank().improves_over(&flop.rank()));
+
+        // A low card that doesn't play leaves the rank unchanged.
+        let blank = flop
+            .with_added(&[Card::try_from_str("h3").unwrap()])
+            .unwrap();
+        assert!(!blank.rank().improves_over(&flop.rank()));
+        assert!(!flop.rank().improves_over(&improved.rank()));
+    }
+
+    #[test]
+    fn test_cmp_payload() {
+        let stored: Vec<(RankCategory, u16)> =
+            ["SA HA DK CK SQ", "ST H9 D8 C7 S6", "SA HK DQ CJ S9"]
+                .iter()
+                .map(|h| {
+                    let rank = rank_of(h);
+                    (rank.category(), rank.payload())
+                })
+                .collect();
+        let rank = Hand::new_from_strs(&["s2", "h2", "dk", "ck", "sq"])
+            .unwrap()
+            .rank();
+        let ordering: Vec<Ordering> = stored
+            .iter()
+            .map(|&(category, payload)| rank.cmp_payload(category, payload))
+            .collect();
+        assert_eq!(
+            vec![Ordering::Less, Ordering::Less, Ordering::Greater],
+            ordering
+        );
+        assert_eq!(
+            Ordering::Equal,
+            rank.cmp_payload(rank.category(), rank.payload())
+        );
+        // Any flush beats any straight, whatever the payloads.
+        assert_eq!(
+            Ordering::Greater,
+            Rank::Flush(0).cmp_payload(RankCategory::Straight, 9)
+        );
+    }
+
+    #[test]
+    fn test_best_and_worst_hand() {
+        let hands = || {
+            [
+                ["s2", "h2", "d9", "c7", "sk"],
+                ["sa", "ha", "da", "c7", "sk"],
+                ["s3", "h5", "d9", "c7", "sk"],
+                ["sa", "ha", "da", "c7", "dk"],
+            ]
+            .map(|h| Hand::new_from_strs(&h).unwrap())
+        };
+
+        let (idx, rank) = best_hand(hands()).unwrap();
+        assert_eq!(1, idx);
+        assert!(rank.is_three_of_a_kind());
+
+        let (idx, rank) = worst_hand(hands()).unwrap();
+        assert_eq!(2, idx);
+        assert!(rank.is_high_card());
+
+        assert_eq!(None, best_hand(Vec::new()));
+    }
+
+    #[test]
+    fn test_group_key() {
+        let trips = |h: [&str; 5]| Hand::new_from_strs(&h).unwrap().rank();
+        let a = trips(["sk", "hk", "dk", "c7", "s2"]);
+        let b = trips(["sk", "hk", "dk", "ca", "sq"]);
+        assert_eq!(a.group_key(), b.group_key());
+        assert_ne!(a.score(), b.score());
+        assert!(a.score() < b.score());
+
+        let queens = trips(["sq", "hq", "dq", "ca", "sj"]);
+        assert_ne!(a.group_key(), queens.group_key());
+
+        let two_pair = trips(["sk", "hk", "d7", "c7", "s2"]);
+        let other = trips(["sk", "hk", "d7", "c7", "sa"]);
+        assert_eq!(two_pair.group_key(), other.group_key());
+        assert_ne!(a.group_key(), two_pair.group_key());
+        assert_eq!(0, Rank::HighCard(0).group_key());
+    }
+
+    #[test]
+    fn test_group_key_short_hands() {
+        let key = |h: &[&str]| Hand::new_from_strs(h).unwrap().rank().group_key();
+        assert_ne!(key(&["sa", "ha"]), key(&["s2", "h2"]));
+        assert_eq!(key(&["sa", "ha"]), key(&["sa", "ha", "dk", "c7", "s2"]));
+        assert_ne!(key(&["sa", "ha", "da"]), key(&["s2", "h2", "d2"]));
+        assert_eq!(
+            key(&["sa", "ha", "da"]),
+            key(&["sa", "ha", "da", "c7", "s2"])
+        );
+        assert_ne!(
+            key(&["sk", "hk", "dk", "ck"]),
+            key(&["s2", "h2", "d2", "c2"])
+        );
+        assert_eq!(
+            key(&["sk", "hk", "dk", "ck"]),
+            key(&["sk", "hk", "dk", "ck", "sa"])
+        );
+    }
+
+    #[test]
+    fn test_score_order() {
+        let mut ranks = DISTINCT_RANKS.clone();
+        ranks.sort_by_key(|r| r.score());
+        assert_eq!(*DISTINCT_RANKS, ranks);
+    }
+
+    #[test]
+    fn test_rank_no_straight_flush() {
+        let royal = Hand::new_from_strs(&["sa", "sk", "sq", "sj", "st"]).unwrap();
+        assert_eq!(
+            Rank::HighCard(0b1_1111_0000_0000),
+            royal.rank_no_straight_flush()
+        );
+        assert!(royal.rank_no_straight_flush().is_high_card());
+
+        let boat = Hand::new_from_strs(&["s9", "h9", "d9", "ca", "sa"]).unwrap();
+        assert_eq!(boat.rank(), boat.rank_no_straight_flush());
+
+        let flush_pair = Hand::new_from_strs(&["s2", "s5", "s9", "sj", "sa", "ha"]).unwrap();
+        assert!(flush_pair.rank_no_straight_flush().is_one_pair());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_readable_rank_serde() {
+        let boat = Hand::new_from_strs(&["s9", "h9", "d9", "ca", "sa"]).unwrap();
+        let json = serde_json::to_string(&ReadableRank(boat.rank())).unwrap();
+        assert_eq!(
+            r#"{"category":"FullHouse","description":"Full House, Nines full of Aces"}"#,
+            json
+        );
+        let back: ReadableRank = serde_json::from_str(&json).unwrap();
+        assert_eq!(boat.rank(), back.0);
+
+        let wheel = Rank::Straight(0);
+        let json = serde_json::to_string(&ReadableRank(wheel.clone())).unwrap();
+        assert_eq!(
+            wheel,
+            serde_json::from_str::<ReadableRank>(&json).unwrap().0
+        );
+
+        // Kickers are lost, the best matching rank comes back.
+        let pair = Hand::new_from_strs(&["sa", "ha", "d2", "c3", "s4"]).unwrap();
+        let json = serde_json::to_string(&ReadableRank(pair.rank())).unwrap();
+        let back: ReadableRank = serde_json::from_str(&json).unwrap();
+        assert_eq!(pair.rank().describe(), back.0.describe());
+        assert!(back.0 > pair.rank());
+
+        let bad = r#"{"category":"Flush","description":"Full House, Nines full of Aces"}"#;
+        assert!(serde_json::from_str::<ReadableRank>(bad).is_err());
     }
 }
@@ -0,0 +1,245 @@
+use crate::error::{Error, Result};
+
+use super::{
+    card::Card,
+    hand::Hand,
+    rank::{compare_ranks, HandRanker, Rank},
+};
+
+/// The outcome of comparing several players' hands.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ShowdownResult {
+    winners: Vec<usize>,
+    ranks: Vec<Rank>,
+    descriptions: Vec<String>,
+    /// Player names in input order, empty when the players aren't named.
+    names: Vec<String>,
+}
+
+impl ShowdownResult {
+    /// Builds the result from each player's rank, in input order.
+    pub fn from_ranks(ranks: Vec<Rank>) -> Self {
+        let winners = compare_ranks(&ranks);
+        let descriptions = ranks.iter().map(|r| r.describe()).collect();
+        Self {
+            winners,
+            ranks,
+            descriptions,
+            names: Vec::new(),
+        }
+    }
+
+    /// Like `from_ranks`, naming each player in the same order.
+    pub fn from_named_ranks(names: Vec<String>, ranks: Vec<Rank>) -> Self {
+        Self {
+            names,
+            ..Self::from_ranks(ranks)
+        }
+    }
+
+    /// Indices of the winning players, more than one on a split pot.
+    pub fn winners(&self) -> &[usize] {
+        &self.winners
+    }
+
+    /// Each player's best rank, in input order.
+    pub fn ranks(&self) -> &[Rank] {
+        &self.ranks
+    }
+
+    /// Each player's `Rank::describe()`, in input order.
+    pub fn descriptions(&self) -> &[String] {
+        &self.descriptions
+    }
+
+    /// Each player's name, in input order, or nothing when the players
+    /// weren't named.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Names of the winning players, empty when the players weren't named.
+    pub fn winner_names(&self) -> Vec<&str> {
+        self.winners
+            .iter()
+            .filter_map(|&i| self.names.get(i).map(String::as_str))
+            .collect()
+    }
+}
+
+/// Evaluates each player's cards, given as card strings like `["SA", "HK"]`,
+/// and reports who wins and with what.
+pub fn showdown(players: &[&[&str]]) -> Result<ShowdownResult> {
+    let ranks = players
+        .iter()
+        .map(|strs| Ok(Hand::new_from_strs(strs)?.rank()))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(ShowdownResult::from_ranks(ranks))
+}
+
+/// Evaluates a showdown written as text, one `name: cards` line per
+/// player and an optional `board: cards` line shared by all of them:
+///
+/// ```text
+/// board: Ks 7d 2c 9h Th
+/// alice: As Kd
+/// bob: Qh Qs
+/// ```
+///
+/// Cards may be in any notation `Hand::from_mixed_notation` accepts. Blank
+/// lines and lines starting with `#` are skipped. Players are numbered in
+/// the order of their lines and keep their names in the result.
+///
+/// # Errors
+///
+/// * `Error::MalformedShowdownLine` - If a line has no `:`, there's a second
+///   board, or there are no players.
+/// * `Error::UnexpectedCardChar` - If a card can't be parsed.
+/// * `Error::DuplicateCardInHand` - If a card is dealt twice.
+pub fn parse_showdown(text: &str) -> Result<ShowdownResult> {
+    let mut board: Option<Hand> = None;
+    let mut holes: Vec<Hand> = Vec::new();
+    let mut names: Vec<String> = Vec::new();
+    let mut last_line = 0;
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        last_line = i + 1;
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (name, cards) = line
+            .split_once(':')
+            .ok_or(Error::MalformedShowdownLine(i + 1))?;
+        let cards = Hand::from_mixed_notation(cards)?;
+        if name.trim().eq_ignore_ascii_case("board") {
+            if board.replace(cards).is_some() {
+                return Err(Error::MalformedShowdownLine(i + 1));
+            }
+        } else {
+            holes.push(cards);
+            names.push(name.trim().to_string());
+        }
+    }
+    if holes.is_empty() {
+        return Err(Error::MalformedShowdownLine(last_line));
+    }
+
+    let board: Vec<Card> = board.map(|b| b.cards().to_vec()).unwrap_or_default();
+    let mut seen: Vec<&Card> = Vec::new();
+    for c in holes.iter().flat_map(|h| h.iter()).chain(&board) {
+        if seen.contains(&c) {
+            return Err(Error::DuplicateCardInHand(c.clone()));
+        }
+        seen.push(c);
+    }
+
+    let ranks: Vec<Rank> = holes
+        .iter()
+        .map(|hole| [hole.cards(), &board].concat().rank())
+        .collect();
+    Ok(ShowdownResult::from_named_ranks(names, ranks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_three_players() {
+        let result = showdown(&[
+            &["sa", "ha", "d9", "c9", "s8", "h7", "dk"],
+            &["s5", "h6", "d9", "c9", "s8", "h7", "dk"],
+            &["ck", "hk", "d9", "c9", "s8", "h7", "dk"],
+        ])
+        .unwrap();
+
+        assert_eq!(&[2], result.winners());
+        assert_eq!(
+            &[
+                "Two Pair, Aces and Nines".to_string(),
+                "Straight, Nine high".to_string(),
+                "Full House, Kings full of Nines".to_string(),
+            ],
+            result.descriptions()
+        );
+        assert!(result.ranks()[2].is_full_house());
+    }
+
+    #[test]
+    fn test_split_pot() {
+        let result = showdown(&[
+            &["s2", "h3", "st", "hj", "dq", "ck", "sa"],
+            &["d2", "c3", "st", "hj", "dq", "ck", "sa"],
+        ])
+        .unwrap();
+        assert_eq!(&[0, 1], result.winners());
+    }
+
+    #[test]
+    fn test_invalid_cards() {
+        assert!(showdown(&[&["sa", "xx"]]).is_err());
+    }
+
+    #[test]
+    fn test_parse_showdown() {
+        let text = "
+            # River
+            board: Ks 7d 2c 9h Th
+            alice: As Kd
+            bob: ♥Q ♠Q
+        ";
+        let result = parse_showdown(text).unwrap();
+        assert_eq!(&[0], result.winners());
+        assert_eq!("Pair of Kings", result.descriptions()[0]);
+        assert_eq!("Pair of Queens", result.descriptions()[1]);
+        assert_eq!(&["alice".to_string(), "bob".to_string()], result.names());
+        assert_eq!(vec!["alice"], result.winner_names());
+    }
+
+    #[test]
+    fn test_parse_showdown_errors() {
+        let result = parse_showdown("board: Ks 7d 2c\nalice As Kd\n");
+        assert!(matches!(result, Err(Error::MalformedShowdownLine(2))));
+
+        let result = parse_showdown("board: Ks 7d 2c\nboard: 9h\nalice: As Kd");
+        assert!(matches!(result, Err(Error::MalformedShowdownLine(2))));
+
+        let result = parse_showdown("board: Ks 7d 2c\n");
+        assert!(matches!(result, Err(Error::MalformedShowdownLine(1))));
+
+        let result = parse_showdown("board: Ks 7d 2c\nalice: As Kd\nbob: As Qd");
+        assert!(matches!(result, Err(Error::DuplicateCardInHand(_))));
+
+        let result = parse_showdown("alice: As Kx");
+        assert!(matches!(result, Err(Error::UnexpectedCardChar)));
+    }
+
+    #[test]
+    fn test_from_ranks() {
+        let hands: [&[&str]; 2] = [
+            &["sa", "ha", "dk", "cq", "sj"],
+            &["s2", "s3", "s4", "s5", "s6"],
+        ];
+        let ranks: Vec<Rank> = hands
+            .iter()
+            .map(|h| Hand::new_from_strs(h).unwrap().rank())
+            .collect();
+        let result = ShowdownResult::from_ranks(ranks.clone());
+        assert_eq!(&[1], result.winners());
+        assert_eq!(&ranks[..], result.ranks());
+        assert_eq!("Straight Flush, Six high", result.descriptions()[1]);
+        assert_eq!(result, showdown(&hands).unwrap());
+
+        assert!(result.names().is_empty());
+        assert!(result.winner_names().is_empty());
+
+        let tied = ShowdownResult::from_ranks(vec![ranks[0].clone(), ranks[0].clone()]);
+        assert_eq!(&[0, 1], tied.winners());
+    }
+
+    #[test]
+    fn test_parse_showdown_split_names() {
+        let result = parse_showdown("board: As Ks Qs Js Ts\ncarol: 2c 3d\ndave: 4h 5c").unwrap();
+        assert_eq!(vec!["carol", "dave"], result.winner_names());
+    }
+}
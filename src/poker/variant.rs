@@ -0,0 +1,191 @@
+use crate::error::{Error, Result};
+
+use super::{
+    card::{Card, Rank as CardRank},
+    rank::{HandRanker, Rank},
+};
+
+/// The games the crate can evaluate, each with its own rules for which
+/// cards make a hand and how hands are ranked.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default)]
+pub enum GameVariant {
+    /// Best five of two hole cards and the board.
+    #[default]
+    TexasHoldem,
+    /// Hold'em with a 36 card (6+) deck, where A-6-7-8-9 is a straight and
+    /// a flush beats a full house.
+    ShortDeck,
+    /// Exactly two of the four hole cards and three of the board.
+    Omaha,
+    /// Five card lowball where straights and flushes count against the
+    /// hand and aces are high.
+    Deuce7Lowball,
+    /// Five card lowball where straights and flushes don't count and the
+    /// fewer pairs the better.
+    AceToFiveLowball,
+}
+
+/// Number of hole cards in an Omaha hand.
+const OMAHA_HOLE_CARDS: usize = 4;
+
+/// Number of cards in a lowball hand.
+const LOWBALL_CARDS: usize = 5;
+
+/// Ranks the cards under the rules of `variant`. Deuce-to-seven lowball
+/// has no board, so its five cards may be split between `hole` and `board`
+/// in any way.
+///
+/// The returned `Rank` is always the high hand the cards make; compare the
+/// categories with `RankCategory::cmp_for` to order them the way the game
+/// does. Ace-to-five lowball counts the ace low, which a `Rank` can't, so
+/// it isn't supported here; see `poker::low` for ace-to-five lows.
+///
+/// # Errors
+///
+/// * `Error::InvalidHandSize` - If a hold'em hand doesn't have 2 hole cards.
+/// * `Error::InvalidBoardSize` - If the board is too big, or too small for
+///   Omaha.
+/// * `Error::WrongCardCount` - If an Omaha or lowball hand has the wrong
+///   number of cards.
+/// * `Error::RankNotInDeck` - If a short deck hand has a card below six.
+/// * `Error::DuplicateCardInHand` - If a card is repeated.
+/// * `Error::UnsupportedVariant` - For ace-to-five lowball.
+pub fn evaluate(variant: GameVariant, hole: &[Card], board: &[Card]) -> Result<Rank> {
+    let cards = [hole, board].concat();
+    for (i, c) in cards.iter().enumerate() {
+        if cards[i + 1..].contains(c) {
+            return Err(Error::DuplicateCardInHand(c.clone()));
+        }
+    }
+
+    match variant {
+        GameVariant::AceToFiveLowball => Err(Error::UnsupportedVariant(variant)),
+        GameVariant::TexasHoldem | GameVariant::ShortDeck => {
+            if hole.len() != 2 {
+                return Err(Error::InvalidHandSize);
+            }
+            if board.len() > 5 {
+                return Err(Error::InvalidBoardSize);
+            }
+            if variant == GameVariant::TexasHoldem {
+                return Ok(cards.rank());
+            }
+            if let Some(c) = cards.iter().find(|c| *c.rank() < CardRank::Six) {
+                return Err(Error::RankNotInDeck(c.rank().clone()));
+            }
+            Ok(cards.rank_short_deck())
+        }
+        GameVariant::Omaha => {
+            if hole.len() != OMAHA_HOLE_CARDS {
+                return Err(Error::WrongCardCount {
+                    expected: OMAHA_HOLE_CARDS,
+                    found: hole.len(),
+                });
+            }
+            if !(3..=5).contains(&board.len()) {
+                return Err(Error::InvalidBoardSize);
+            }
+            Ok(rank_omaha(hole, board))
+        }
+        GameVariant::Deuce7Lowball => {
+            if cards.len() != LOWBALL_CARDS {
+                return Err(Error::WrongCardCount {
+                    expected: LOWBALL_CARDS,
+                    found: cards.len(),
+                });
+            }
+            Ok(cards.rank_five())
+        }
+    }
+}
+
+/// The best hand using exactly two hole cards and three board cards.
+fn rank_omaha(hole: &[Card], board: &[Card]) -> Rank {
+    let mut best = None;
+    for (i, h1) in hole.iter().enumerate() {
+        for h2 in &hole[i + 1..] {
+            for (j, b1) in board.iter().enumerate() {
+                for (k, b2) in board.iter().enumerate().skip(j + 1) {
+                    for b3 in &board[k + 1..] {
+                        let five = [h1, h2, b1, b2, b3].map(Card::clone);
+                        best = best.max(Some(five.rank_five()));
+                    }
+                }
+            }
+        }
+    }
+    best.expect("Omaha hands always have a five card combination")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poker::hand::Hand;
+
+    fn cards(s: &str) -> Vec<Card> {
+        Hand::from_mixed_notation(s).unwrap().cards().to_vec()
+    }
+
+    #[test]
+    fn test_holdem_and_omaha_differ() {
+        // Hold'em plays the four flush on the board with one heart; Omaha
+        // needs two hearts from the hand.
+        let hole = cards("AhKsQd3c");
+        let board = cards("2h7h9hJhKd");
+        let omaha = evaluate(GameVariant::Omaha, &hole, &board).unwrap();
+        assert!(omaha.is_one_pair());
+        let holdem = evaluate(GameVariant::TexasHoldem, &hole[..2], &board).unwrap();
+        assert!(holdem.is_flush());
+    }
+
+    #[test]
+    fn test_short_deck_straight() {
+        let hole = cards("As6d");
+        let board = cards("7c8h9sKd");
+        let short = evaluate(GameVariant::ShortDeck, &hole, &board).unwrap();
+        assert_eq!(Rank::Straight(0), short);
+        let holdem = evaluate(GameVariant::TexasHoldem, &hole, &board).unwrap();
+        assert!(holdem.is_high_card());
+
+        let result = evaluate(GameVariant::ShortDeck, &cards("As2d"), &board);
+        assert!(matches!(result, Err(Error::RankNotInDeck(CardRank::Two))));
+    }
+
+    #[test]
+    fn test_lowball() {
+        let hand = cards("2s3d4c5h6s");
+        let deuce = evaluate(GameVariant::Deuce7Lowball, &hand[..3], &hand[3..]).unwrap();
+        assert!(deuce.is_straight());
+        assert!(matches!(
+            evaluate(GameVariant::AceToFiveLowball, &hand, &[]),
+            Err(Error::UnsupportedVariant(GameVariant::AceToFiveLowball))
+        ));
+
+        let result = evaluate(GameVariant::Deuce7Lowball, &hand[..4], &[]);
+        assert!(matches!(
+            result,
+            Err(Error::WrongCardCount {
+                expected: 5,
+                found: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let board = cards("2h7h9h");
+        assert!(matches!(
+            evaluate(GameVariant::Omaha, &cards("AhKs"), &board),
+            Err(Error::WrongCardCount { .. })
+        ));
+        assert!(matches!(
+            evaluate(GameVariant::Omaha, &cards("AhKsQd3c"), &board[..2]),
+            Err(Error::InvalidBoardSize)
+        ));
+        assert!(matches!(
+            evaluate(GameVariant::TexasHoldem, &cards("2hKs"), &board),
+            Err(Error::DuplicateCardInHand(_))
+        ));
+    }
+}
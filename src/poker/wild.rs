@@ -0,0 +1,115 @@
+use super::{
+    card::{Card, Rank as CardRank, Suit},
+    deck::Deck,
+    rank::{HandRanker, Rank},
+};
+
+/// A card that may be a joker, for variants played with wild cards.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Eq, Clone, Hash, Debug)]
+pub enum MaybeWild {
+    Card(Card),
+    Joker,
+}
+
+impl MaybeWild {
+    pub fn is_joker(&self) -> bool {
+        matches!(self, MaybeWild::Joker)
+    }
+}
+
+impl From<Card> for MaybeWild {
+    fn from(c: Card) -> Self {
+        MaybeWild::Card(c)
+    }
+}
+
+/// A standard 52 card deck plus the given number of jokers.
+pub fn deck_with_jokers(jokers: usize) -> Vec<MaybeWild> {
+    let mut cards: Vec<MaybeWild> = Deck::default()
+        .get_all_cards()
+        .into_iter()
+        .map(MaybeWild::Card)
+        .collect();
+    cards.extend(std::iter::repeat_n(MaybeWild::Joker, jokers));
+    cards
+}
+
+/// Ranks the cards with every joker standing in for whichever card not
+/// already in the hand makes the best hand.
+pub fn rank_wild(cards: &[MaybeWild]) -> Rank {
+    let mut fixed: Vec<Card> = cards
+        .iter()
+        .filter_map(|c| match c {
+            MaybeWild::Card(c) => Some(c.clone()),
+            MaybeWild::Joker => None,
+        })
+        .collect();
+    let jokers = cards.len() - fixed.len();
+    best_substitution(&mut fixed, jokers)
+}
+
+fn best_substitution(cards: &mut Vec<Card>, jokers: usize) -> Rank {
+    if jokers == 0 {
+        return cards.rank();
+    }
+
+    let mut best: Option<Rank> = None;
+    for suit in Suit::suits() {
+        for rank in CardRank::ranks() {
+            let c = Card::new(suit.clone(), rank);
+            if cards.contains(&c) {
+                continue;
+            }
+            cards.push(c);
+            let r = best_substitution(cards, jokers - 1);
+            cards.pop();
+            if best.as_ref().is_none_or(|b| r > *b) {
+                best = Some(r);
+            }
+        }
+    }
+    best.unwrap_or_else(|| cards.rank())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poker::hand::Hand;
+
+    fn with_joker(strs: &[&str]) -> Vec<MaybeWild> {
+        let mut cards: Vec<MaybeWild> = Hand::new_from_strs(strs)
+            .unwrap()
+            .iter()
+            .cloned()
+            .map(MaybeWild::from)
+            .collect();
+        cards.push(MaybeWild::Joker);
+        cards
+    }
+
+    #[test]
+    fn test_joker_completes_straight() {
+        let cards = with_joker(&["s5", "h6", "d8", "c9"]);
+        assert_eq!(Rank::Straight(4), rank_wild(&cards));
+    }
+
+    #[test]
+    fn test_joker_completes_four_of_a_kind() {
+        let cards = with_joker(&["sk", "hk", "dk", "c2"]);
+        assert!(rank_wild(&cards).is_four_of_a_kind());
+    }
+
+    #[test]
+    fn test_joker_prefers_straight_flush() {
+        let cards = with_joker(&["h9", "ht", "hj", "hq", "c9", "d9"]);
+        assert_eq!(Rank::StraightFlush(8), rank_wild(&cards));
+    }
+
+    #[test]
+    fn test_deck_with_jokers() {
+        let deck = deck_with_jokers(2);
+        assert_eq!(54, deck.len());
+        assert_eq!(2, deck.iter().filter(|c| c.is_joker()).count());
+    }
+}
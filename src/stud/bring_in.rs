@@ -0,0 +1,44 @@
+use crate::poker::card::Card;
+
+/// Finds the lowest card showing, which is forced to bring in.
+///
+/// Cards are compared by rank with aces high, and ties on rank are broken
+/// by suit (clubs lowest, then diamonds, hearts and spades).
+pub fn lowest_card(cards: &[Card]) -> Option<Card> {
+    cards.iter().min_by_key(|c| (c.rank(), c.suit())).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poker::hand::Hand;
+
+    #[test]
+    fn test_lowest_rank() {
+        let up = Hand::new_from_strs(&["sk", "c9", "d3", "ha"]).unwrap();
+        assert_eq!(
+            Some(Card::try_from_str("d3").unwrap()),
+            lowest_card(up.cards())
+        );
+    }
+
+    #[test]
+    fn test_tie_broken_by_suit() {
+        let up = Hand::new_from_strs(&["s2", "h2", "d2", "c7"]).unwrap();
+        assert_eq!(
+            Some(Card::try_from_str("d2").unwrap()),
+            lowest_card(up.cards())
+        );
+
+        let up = Hand::new_from_strs(&["s2", "c2", "h2"]).unwrap();
+        assert_eq!(
+            Some(Card::try_from_str("c2").unwrap()),
+            lowest_card(up.cards())
+        );
+    }
+
+    #[test]
+    fn test_no_cards() {
+        assert_eq!(None, lowest_card(&[]));
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{
+    error::{Error, Result},
+    poker::{card::Card, deck::Deck},
+};
+
+use super::bring_in::lowest_card;
+
+/// One player's cards in a stud deal.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct StudHand {
+    down: Vec<Card>,
+    up: Vec<Card>,
+}
+
+impl StudHand {
+    /// The hole cards only the player sees.
+    pub fn down(&self) -> &[Card] {
+        &self.down
+    }
+
+    /// The cards showing to the table, the door card first.
+    pub fn up(&self) -> &[Card] {
+        &self.up
+    }
+}
+
+/// The cards dealt to every player and who is forced to bring in.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct StudDeal {
+    hands: Vec<StudHand>,
+    bring_in: Option<usize>,
+}
+
+impl StudDeal {
+    pub fn hands(&self) -> &[StudHand] {
+        &self.hands
+    }
+
+    /// The seat with the lowest door card, or `None` if nothing was dealt up.
+    pub fn bring_in(&self) -> Option<usize> {
+        self.bring_in
+    }
+}
+
+impl Deck {
+    /// Deals `down` hidden and `up` exposed cards to each player, going
+    /// around the table for the down cards first, and finds the bring-in.
+    /// Cards come off the deck in its canonical order, so an unshuffled
+    /// deck always produces the same deal.
+    pub fn deal_stud(&mut self, players: usize, down: usize, up: usize) -> Result<StudDeal> {
+        if self.len() < players * (down + up) {
+            return Err(Error::NotEnoughCards);
+        }
+
+        let mut hands = vec![
+            StudHand {
+                down: Vec::with_capacity(down),
+                up: Vec::with_capacity(up),
+            };
+            players
+        ];
+        for _ in 0..down {
+            for hand in hands.iter_mut() {
+                hand.down
+                    .push(self.deal_card().ok_or(Error::NotEnoughCards)?);
+            }
+        }
+        for _ in 0..up {
+            for hand in hands.iter_mut() {
+                hand.up.push(self.deal_card().ok_or(Error::NotEnoughCards)?);
+            }
+        }
+
+        let doors: Vec<Card> = hands.iter().filter_map(|h| h.up.first().cloned()).collect();
+        let bring_in = lowest_card(&doors).and_then(|low| doors.iter().position(|c| *c == low));
+
+        Ok(StudDeal { hands, bring_in })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::poker::card::{Rank, Suit};
+
+    #[test]
+    fn test_deal_counts() {
+        let mut deck = Deck::default();
+        let deal = deck.deal_stud(4, 2, 1).unwrap();
+        assert_eq!(4, deal.hands().len());
+        for hand in deal.hands() {
+            assert_eq!(2, hand.down().len());
+            assert_eq!(1, hand.up().len());
+        }
+        assert_eq!(52 - 12, deck.len());
+    }
+
+    #[test]
+    fn test_bring_in() {
+        let mut deck = Deck::new();
+        // Dealt in canonical order: seat 0 gets the deuce of clubs, which is
+        // the lowest, seat 1 the deuce of hearts and seat 2 the ten.
+        for (suit, rank) in [
+            (Suit::Spade, Rank::Ten),
+            (Suit::Heart, Rank::Two),
+            (Suit::Club, Rank::Two),
+        ] {
+            deck.insert(Card::new(suit, rank));
+        }
+        let deal = deck.deal_stud(3, 0, 1).unwrap();
+        assert_eq!(Some(0), deal.bring_in());
+        assert_eq!(Card::new(Suit::Club, Rank::Two), deal.hands()[0].up()[0]);
+        assert_eq!(Card::new(Suit::Spade, Rank::Ten), deal.hands()[2].up()[0]);
+    }
+
+    #[test]
+    fn test_not_enough_cards() {
+        let mut deck = Deck::default();
+        assert!(matches!(
+            deck.deal_stud(8, 3, 4),
+            Err(Error::NotEnoughCards)
+        ));
+        assert_eq!(52, deck.len());
+        assert_eq!(None, deck.deal_stud(2, 2, 0).unwrap().bring_in());
+    }
+}
@@ -0,0 +1,2 @@
+pub mod bring_in;
+pub mod deal;